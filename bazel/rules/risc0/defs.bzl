@@ -42,7 +42,35 @@ def risc0_cc_method(name, **kwargs):
         visibility = ["//visibility:public"],
     )
 
-def risc0_rust_method(name, **kwargs):
+_MEMORY_REGIONS = ["stack", "heap", "input", "output", "commit"]
+
+def _memory_layout_script(name, memory):
+    """Prepends the region sizes in `memory` to risc0.ld, which reads them."""
+    lines = []
+    for region, size in memory.items():
+        if region not in _MEMORY_REGIONS:
+            fail("Unknown memory region {}, expected one of {}".format(region, _MEMORY_REGIONS), "memory")
+        lines.append("__{}_size = {};".format(region, size))
+    native.genrule(
+        name = name,
+        srcs = ["//risc0/zkvm/platform:risc0.ld"],
+        outs = [name + ".ld"],
+        cmd = "(printf '%s\\n' {} && cat $<) > $@".format(" ".join(["'{}'".format(line) for line in lines])),
+    )
+
+def risc0_rust_method(name, memory = {}, **kwargs):
+    """Builds a Rust guest method and its method ID.
+
+    Args:
+        name: The name of the method.
+        memory: The sizes in bytes of the memory regions to change from their
+            defaults in risc0.ld, keyed by "stack", "heap", "input", "output" or
+            "commit".
+        **kwargs: Passed to risc0_rust_binary.
+    """
+    if memory:
+        _memory_layout_script(name = name + "_layout", memory = memory)
+        kwargs["linker_script"] = name + "_layout"
     risc0_rust_binary(
         name = name,
         **kwargs
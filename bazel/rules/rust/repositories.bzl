@@ -23,6 +23,11 @@ DEFAULT_TOOLCHAIN_TRIPLES = {
     "x86_64-unknown-linux-gnu": "rust_linux_x86_64",
 }
 
+# Targets whose prebuilt stdlib is downloaded as-is, e.g. for wasm_bindgen.
+DEFAULT_STDLIB_TRIPLES = [
+    "wasm32-unknown-unknown",
+]
+
 def _build_sysroot(ctx, target_triple, target_json):
     target_json = ctx.path(Label(target_json))
     out_dir = "lib/rustlib/{}/lib".format(target_triple)
@@ -90,6 +95,8 @@ def _rust_toolchain_repository_impl(ctx):
         load_llvm_tools(ctx, ctx.attr.exec_triple)
 
     build_components.append(load_rust_stdlib(ctx, ctx.attr.exec_triple))
+    for target_triple in ctx.attr.stdlib_triples:
+        build_components.append(load_rust_stdlib(ctx, target_triple))
     if ctx.attr.dev_components:
         load_rustc_dev_nightly(ctx, ctx.attr.exec_triple)
 
@@ -125,6 +132,17 @@ def _rust_toolchain_repository_proxy_impl(ctx):
         ),
     ]
 
+    for target_triple in ctx.attr.stdlib_triples:
+        build_components.append(BUILD_for_toolchain(
+            name = "{toolchain_prefix}_{target_triple}".format(
+                toolchain_prefix = ctx.attr.toolchain_name_prefix,
+                target_triple = target_triple,
+            ),
+            exec_triple = ctx.attr.exec_triple,
+            parent_workspace_name = ctx.attr.parent_workspace_name,
+            target_triple = target_triple,
+        ))
+
     for target_triple, toolchain in ctx.attr.extra_toolchains.items():
         build_components.append(toolchain.format(
             name = "{toolchain_prefix}_{target_triple}".format(
@@ -148,6 +166,7 @@ rust_toolchain_repository = repository_rule(
         "iso_date": attr.string(),
         "rustfmt_version": attr.string(),
         "sha256s": attr.string_dict(),
+        "stdlib_triples": attr.string_list(),
         "toolchain_name_prefix": attr.string(),
         "urls": attr.string_list(default = DEFAULT_STATIC_RUST_URL_TEMPLATES),
         "version": attr.string(mandatory = True),
@@ -160,6 +179,7 @@ rust_toolchain_repository_proxy = repository_rule(
         "exec_triple": attr.string(mandatory = True),
         "extra_toolchains": attr.string_dict(),
         "parent_workspace_name": attr.string(mandatory = True),
+        "stdlib_triples": attr.string_list(),
         "toolchain_name_prefix": attr.string(),
     },
     implementation = _rust_toolchain_repository_proxy_impl,
@@ -221,6 +241,7 @@ rust_toolchain(
         rustfmt_version = rustfmt_version,
         edition = edition,
         dev_components = dev_components,
+        stdlib_triples = DEFAULT_STDLIB_TRIPLES,
         urls = urls,
     )
 
@@ -244,11 +265,12 @@ toolchain(
         exec_triple = exec_triple,
         extra_toolchains = extra_toolchains,
         parent_workspace_name = name,
+        stdlib_triples = DEFAULT_STDLIB_TRIPLES,
         toolchain_name_prefix = DEFAULT_TOOLCHAIN_NAME_PREFIX,
     )
 
     all_toolchain_names = []
-    for target_triple in [exec_triple] + extra_toolchains.keys():
+    for target_triple in [exec_triple] + DEFAULT_STDLIB_TRIPLES + extra_toolchains.keys():
         all_toolchain_names.append("@{name}_toolchains//:{toolchain_name_prefix}_{triple}".format(
             name = name,
             toolchain_name_prefix = DEFAULT_TOOLCHAIN_NAME_PREFIX,
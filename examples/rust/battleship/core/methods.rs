@@ -0,0 +1,33 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Typed handles for the battleship guest methods, for use by hosts.
+
+use battleship_core::{GameState, RoundCommit, RoundParams, RoundResult};
+use zkvm_core::Digest;
+
+zkvm_host::methods! {
+    /// Checks a game state and commits to its digest.
+    pub INIT(GameState) -> ((), Digest) = (
+        "examples/rust/battleship/core/init",
+        include_bytes!(env!("INIT_ID")),
+    );
+
+    /// Plays a shot against a game state, writing the new state and
+    /// committing to the round.
+    pub TURN(RoundParams) -> (RoundResult, RoundCommit) = (
+        "examples/rust/battleship/core/turn",
+        include_bytes!(env!("TURN_ID")),
+    );
+}
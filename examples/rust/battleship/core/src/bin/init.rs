@@ -15,16 +15,15 @@
 #![no_main]
 #![no_std]
 
-use zkvm_guest::{env, sha};
+use zkvm_core::Digest;
+use zkvm_guest::sha;
 
 use battleship_core::GameState;
 
-zkvm_guest::entry!(main);
-
-pub fn main() {
-    let state: GameState = env::read();
+#[zkvm_guest::main]
+pub fn main(state: GameState) -> Digest {
     if !state.check() {
         panic!("Invalid GameState");
     }
-    env::commit(&sha::digest(state));
+    *sha::digest(state)
 }
@@ -19,16 +19,14 @@ use zkvm_guest::{env, sha};
 
 use battleship_core::{RoundCommit, RoundParams};
 
-zkvm_guest::entry!(main);
-
-pub fn main() {
-    let params: RoundParams = env::read();
+#[zkvm_guest::main]
+pub fn main(params: RoundParams) -> RoundCommit {
     let result = params.process();
     env::write(&result);
-    env::commit(&RoundCommit {
+    RoundCommit {
         old_state: *sha::digest(params.state),
         new_state: *sha::digest(result.state),
         shot: params.shot,
         hit: result.hit,
-    });
+    }
 }
@@ -12,13 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use battleship_core::{GameState, HitType, Position, RoundCommit, RoundParams, RoundResult};
+use battleship_core::{GameState, HitType, Position, RoundCommit, RoundParams};
+use battleship_methods::{INIT, TURN};
 use zkvm_core::Digest;
-use zkvm_host::{Exception, Prover, Receipt, Result};
-use zkvm_serde::{from_slice, to_vec};
+use zkvm_host::{Exception, ProverOpts, Result, TypedReceipt};
 
 pub struct InitMessage {
-    receipt: Receipt,
+    receipt: TypedReceipt<Digest>,
 }
 
 #[derive(Debug)]
@@ -27,7 +27,7 @@ pub struct TurnMessage {
 }
 
 pub struct RoundMessage {
-    receipt: Receipt,
+    receipt: TypedReceipt<RoundCommit>,
 }
 
 #[derive(Debug)]
@@ -37,20 +37,6 @@ pub struct Battleship {
     peer_state: Digest,
 }
 
-impl InitMessage {
-    pub fn get_state(&self) -> Result<Digest> {
-        let msg = self.receipt.get_journal_vec()?;
-        Ok(from_slice(msg.as_slice()).unwrap())
-    }
-}
-
-impl RoundMessage {
-    pub fn get_commit(&self) -> Result<RoundCommit> {
-        let msg = self.receipt.get_journal_vec()?;
-        Ok(from_slice(msg.as_slice()).unwrap())
-    }
-}
-
 impl Battleship {
     pub fn new(state: GameState) -> Self {
         Battleship {
@@ -61,17 +47,13 @@ impl Battleship {
     }
 
     pub fn init(&self) -> Result<InitMessage> {
-        let mut prover = Prover::new("examples/rust/battleship/core/init")?;
-        let vec = to_vec(&self.state).unwrap();
-        prover.add_input(vec.as_slice())?;
-        let receipt = prover.run()?;
+        let receipt = INIT.prove(&self.state)?;
         Ok(InitMessage { receipt })
     }
 
     pub fn on_init_msg(&mut self, msg: &InitMessage) -> Result<()> {
         log::info!("on_init_msg");
-        msg.receipt.verify("examples/rust/battleship/core/init")?;
-        self.peer_state = msg.get_state()?;
+        self.peer_state = msg.receipt.verify()?;
         log::info!("  peer_state: {:?}", self.peer_state);
         Ok(())
     }
@@ -86,20 +68,14 @@ impl Battleship {
     pub fn on_turn_msg(&mut self, msg: &TurnMessage) -> Result<RoundMessage> {
         log::info!("on_turn_msg: {:?}", msg);
         let params = RoundParams::new(self.state.clone(), msg.shot.x, msg.shot.y);
-        let mut prover = Prover::new("examples/rust/battleship/core/turn")?;
-        let vec = to_vec(&params).unwrap();
-        prover.add_input(vec.as_slice())?;
-        let receipt = prover.run()?;
-        let vec = prover.get_output_vec()?;
-        let result = from_slice::<RoundResult>(vec.as_slice()).unwrap();
-        self.state = result.state.clone();
+        let (receipt, result) = TURN.prove_with_opts(&params, ProverOpts::default())?;
+        self.state = result.state;
         Ok(RoundMessage { receipt })
     }
 
     pub fn on_round_msg(&mut self, msg: &RoundMessage) -> Result<HitType> {
         log::info!("on_round_msg");
-        msg.receipt.verify("examples/rust/battleship/core/turn")?;
-        let commit = msg.get_commit()?;
+        let commit = msg.receipt.verify()?;
         log::info!("  commit: {:?}", commit);
 
         if commit.old_state != self.peer_state {
@@ -131,7 +107,7 @@ impl Battleship {
 #[cfg(test)]
 mod tests {
     use battleship_core::{Ship, ShipDirection};
-    use zkvm_serde::to_slice;
+    use zkvm_serde::{from_slice, to_slice};
 
     use super::*;
 
@@ -144,14 +120,6 @@ mod tests {
 
     #[test]
     fn serde() {
-        struct Logger;
-        impl zkvm_core::Log for Logger {
-            fn log(&self, msg: &str) {
-                log::info!("{}", msg);
-            }
-        }
-        static LOGGER: Logger = Logger;
-        zkvm_core::set_logger(&LOGGER);
         let commit = RoundCommit {
             old_state: Digest::new([0, 1, 2, 3, 4, 5, 6, 7]),
             new_state: Digest::new([8, 7, 6, 5, 4, 3, 2, 1]),
@@ -25,14 +25,14 @@ pub struct Receipt {
     seal: Vec<u32>,
 }
 
-fn do_proof<T>(name: &str, input: T) -> Result<String, zkvm_host::Exception>
+async fn do_proof<T>(name: &str, input: T) -> Result<String, zkvm_host::Exception>
 where
     T: Serialize,
 {
     let mut prover = Prover::new(name)?;
     let vec = zkvm_serde::to_vec(&input).unwrap();
     prover.add_input(vec.as_slice())?;
-    let c_receipt = prover.run()?;
+    let (_, c_receipt) = zkvm_host_tokio::prove_async(prover).await?;
     let receipt = Receipt {
         journal: c_receipt.get_journal().unwrap().to_vec(),
         seal: c_receipt.get_seal().unwrap().to_vec(),
@@ -41,7 +41,7 @@ where
 }
 
 async fn prove_init(Json(payload): Json<GameState>) -> impl IntoResponse {
-    let out = match do_proof("examples/rust/battleship/core/init", payload) {
+    let out = match do_proof("examples/rust/battleship/core/init", payload).await {
         Ok(receipt) => receipt,
         Err(_e) => {
             return (
@@ -22,6 +22,7 @@ use zkvm_core::Digest;
 
 #[derive(Clone, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Message {
+    #[serde(with = "zkvm_serde::packed")]
     pub msg: [u8; 32],
 }
 
@@ -45,6 +46,7 @@ impl Debug for Message {
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Passphrase {
+    #[serde(with = "zkvm_serde::packed")]
     pub pass: [u8; 32],
 }
 
@@ -16,6 +16,8 @@ use clap::{Arg, Command};
 use lib::sign;
 
 fn main() {
+    env_logger::init();
+
     let matches = Command::new("sign")
         .version("0.1.0")
         .author("Risc0, Inc.")
@@ -14,7 +14,10 @@
 
 #include "risc0/core/elf.h"
 
+#include <algorithm>
+#include <cstring>
 #include <fstream>
+#include <iterator>
 #include <set>
 #include <sstream>
 #include <vector>
@@ -55,9 +58,94 @@ struct ProgHeader {
   uint32_t p_align;
 };
 
+struct SectionHeader {
+  uint32_t sh_name;
+  uint32_t sh_type;
+  uint32_t sh_flags;
+  uint32_t sh_addr;
+  uint32_t sh_offset;
+  uint32_t sh_size;
+  uint32_t sh_link;
+  uint32_t sh_info;
+  uint32_t sh_addralign;
+  uint32_t sh_entsize;
+};
+
+struct SymbolEntry {
+  uint32_t st_name;
+  uint32_t st_value;
+  uint32_t st_size;
+  uint8_t st_info;
+  uint8_t st_other;
+  uint16_t st_shndx;
+};
+
+constexpr uint32_t kSectionSymTab = 2;
+constexpr uint32_t kSectionNoBits = 8;
+constexpr uint8_t kSymbolFunc = 2;
+
+// Copies 'len' bytes at 'offset' of the ELF image into 'out', or throws if out of bounds.
+void readBytes(const std::vector<uint8_t>& elf, size_t offset, void* out, size_t len) {
+  if (offset > elf.size() || len > elf.size() - offset) {
+    throw std::runtime_error("ELF read out of bounds");
+  }
+  std::memcpy(out, elf.data() + offset, len);
+}
+
+// Reads the section headers of the ELF image, along with its main header.
+std::vector<SectionHeader> readSections(const std::vector<uint8_t>& elf, ElfHeader& elfHeader) {
+  readBytes(elf, 0, &elfHeader, sizeof(ElfHeader));
+  if (elfHeader.e_shnum == 0) {
+    return {};
+  }
+  if (elfHeader.e_shentsize != sizeof(SectionHeader)) {
+    throw std::runtime_error("Invalid section header size");
+  }
+  std::vector<SectionHeader> sections(elfHeader.e_shnum);
+  readBytes(elf, elfHeader.e_shoff, sections.data(), sections.size() * sizeof(SectionHeader));
+  return sections;
+}
+
+// Returns the NUL-terminated string at 'index' of the string table 'strtab'.
+std::string readString(const std::vector<uint8_t>& elf, const SectionHeader& strtab, size_t index) {
+  if (strtab.sh_offset > elf.size() || strtab.sh_size > elf.size() - strtab.sh_offset) {
+    throw std::runtime_error("ELF read out of bounds");
+  }
+  const char* strBegin = reinterpret_cast<const char*>(elf.data()) + strtab.sh_offset;
+  const char* strEnd = strBegin + strtab.sh_size;
+  // Names are NUL-terminated, but may not run past the end of the string table.
+  const char* name = strBegin + std::min(index, size_t(strtab.sh_size));
+  return std::string(name, std::find(name, strEnd, '\0'));
+}
+
+// Calls 'fn' with the name and entry of each named symbol of the ELF image.
+template <typename Fn> void forEachSymbol(const std::vector<uint8_t>& elf, Fn fn) {
+  using namespace std;
+  ElfHeader elfHeader;
+  vector<SectionHeader> sections = readSections(elf, elfHeader);
+
+  for (const auto& symtab : sections) {
+    if (symtab.sh_type != kSectionSymTab) {
+      continue;
+    }
+    if (symtab.sh_link >= sections.size()) {
+      throw runtime_error("Invalid symbol string table");
+    }
+    const SectionHeader& strtab = sections[symtab.sh_link];
+    vector<SymbolEntry> entries(symtab.sh_size / sizeof(SymbolEntry));
+    readBytes(elf, symtab.sh_offset, entries.data(), entries.size() * sizeof(SymbolEntry));
+    for (const auto& entry : entries) {
+      if (entry.st_name == 0 || entry.st_name >= strtab.sh_size) {
+        continue;
+      }
+      fn(readString(elf, strtab, entry.st_name), entry);
+    }
+  }
+}
+
 } // namespace
 
-uint32_t loadElf(const std::string& name, uint32_t maxMem, std::map<uint32_t, uint32_t>& memOut) {
+std::vector<uint8_t> readElf(const std::string& name) {
   using namespace std;
   ifstream is;
   is.open(name, ios::binary);
@@ -66,10 +154,21 @@ uint32_t loadElf(const std::string& name, uint32_t maxMem, std::map<uint32_t, ui
     ss << "Could not load ELF: " << name;
     throw std::runtime_error(ss.str());
   }
+  return vector<uint8_t>((istreambuf_iterator<char>(is)), istreambuf_iterator<char>());
+}
+
+uint32_t loadElf(const std::string& name, uint32_t maxMem, std::map<uint32_t, uint32_t>& memOut) {
+  return loadElf(readElf(name), maxMem, memOut);
+}
+
+uint32_t loadElf(const std::vector<uint8_t>& elf,
+                 uint32_t maxMem,
+                 std::map<uint32_t, uint32_t>& memOut) {
+  using namespace std;
   ElfHeader elfHeader;
   vector<ProgHeader> progHeaders;
   // Load the main ELF header
-  is.read(reinterpret_cast<char*>(&elfHeader), sizeof(ElfHeader));
+  readBytes(elf, 0, &elfHeader, sizeof(ElfHeader));
   // Validate all the relevant fields
   if (elfHeader.ei_magic[0] != 0x7f || //
       elfHeader.ei_magic[1] != 'E' ||  //
@@ -100,9 +199,7 @@ uint32_t loadElf(const std::string& name, uint32_t maxMem, std::map<uint32_t, ui
   }
   // Load the program headers
   progHeaders.resize(elfHeader.e_phnum);
-  is.seekg(elfHeader.e_phoff, ios::beg);
-  is.read(reinterpret_cast<char*>(progHeaders.data()),
-          static_cast<streamsize>(progHeaders.size() * sizeof(ProgHeader)));
+  readBytes(elf, elfHeader.e_phoff, progHeaders.data(), progHeaders.size() * sizeof(ProgHeader));
 
   // Go to each program header and load it if relevant
   for (size_t i = 0; i < progHeaders.size(); i++) {
@@ -124,8 +221,6 @@ uint32_t loadElf(const std::string& name, uint32_t maxMem, std::map<uint32_t, ui
     if (phdr.p_filesz > phdr.p_memsz) {
       throw runtime_error("Program header may not be larger in file than in memory");
     }
-    // Seek to position in file
-    is.seekg(phdr.p_offset, ios::beg);
     // Load in memory 4 bytes at a time
     for (uint32_t i = 0; i < phdr.p_memsz; i += 4) {
       if (memOut.count(phdr.p_vaddr + i)) {
@@ -138,7 +233,7 @@ uint32_t loadElf(const std::string& name, uint32_t maxMem, std::map<uint32_t, ui
       }
       uint8_t buf[4] = {0, 0, 0, 0};
       size_t toRead = min(phdr.p_filesz - i, uint32_t(4));
-      is.read(reinterpret_cast<char*>(buf), static_cast<streamsize>(toRead));
+      readBytes(elf, size_t(phdr.p_offset) + i, buf, toRead);
       uint32_t word = uint32_t(buf[0]) | (uint32_t(buf[1]) << 8) | (uint32_t(buf[2]) << 16) |
                       (uint32_t(buf[3]) << 24);
       memOut[phdr.p_vaddr + i] = word;
@@ -147,4 +242,35 @@ uint32_t loadElf(const std::string& name, uint32_t maxMem, std::map<uint32_t, ui
   return elfHeader.e_entry;
 }
 
+std::vector<ElfSymbol> loadElfSymbols(const std::vector<uint8_t>& elf) {
+  std::vector<ElfSymbol> symbols;
+  forEachSymbol(elf, [&](std::string name, const SymbolEntry& entry) {
+    if ((entry.st_info & 0xf) == kSymbolFunc) {
+      symbols.push_back(ElfSymbol{std::move(name), entry.st_value, entry.st_size});
+    }
+  });
+  std::sort(symbols.begin(), symbols.end(), [](const ElfSymbol& lhs, const ElfSymbol& rhs) {
+    return lhs.addr < rhs.addr;
+  });
+  return symbols;
+}
+
+std::vector<uint8_t> loadElfSection(const std::vector<uint8_t>& elf, const std::string& name) {
+  ElfHeader elfHeader;
+  std::vector<SectionHeader> sections = readSections(elf, elfHeader);
+  if (elfHeader.e_shstrndx >= sections.size()) {
+    return {};
+  }
+  const SectionHeader& shstrtab = sections[elfHeader.e_shstrndx];
+  for (const auto& section : sections) {
+    if (section.sh_type == kSectionNoBits || readString(elf, shstrtab, section.sh_name) != name) {
+      continue;
+    }
+    std::vector<uint8_t> contents(section.sh_size);
+    readBytes(elf, section.sh_offset, contents.data(), contents.size());
+    return contents;
+  }
+  return {};
+}
+
 } // namespace risc0
@@ -17,6 +17,7 @@
 #include <cstdint>
 #include <map>
 #include <string>
+#include <vector>
 
 namespace risc0 {
 
@@ -27,4 +28,26 @@ namespace risc0 {
 
 uint32_t loadElf(const std::string& name, uint32_t maxMem, std::map<uint32_t, uint32_t>& memOut);
 
+// As above, but parses an ELF image that has already been read into memory.
+uint32_t loadElf(const std::vector<uint8_t>& elf,
+                 uint32_t maxMem,
+                 std::map<uint32_t, uint32_t>& memOut);
+
+// Reads an ELF file into memory, throwing std::runtime_error if it cannot be read.
+std::vector<uint8_t> readElf(const std::string& name);
+
+// A function from the symbol table of an ELF file.
+struct ElfSymbol {
+  std::string name;
+  uint32_t addr;
+  uint32_t size;
+};
+
+// Returns the function symbols of an ELF image ordered by address, or none if it is stripped.
+std::vector<ElfSymbol> loadElfSymbols(const std::vector<uint8_t>& elf);
+
+// Returns the contents of the section of an ELF image called 'name', or nothing if there is no such
+// section.
+std::vector<uint8_t> loadElfSection(const std::vector<uint8_t>& elf, const std::string& name);
+
 } // namespace risc0
@@ -121,7 +121,10 @@ public:
     // We use rejection sampling to make sure the results are truely uniform.  Basically, if we are
     // in the final uneven remainder of 2^64 / P, we just pull a new random number.  The propability
     // of such is case is less than 1 in 2^32, so this is a rare event.
-    uint64_t val = uint64_t(rng.generate()) << 32 | rng.generate();
+    // The draws are sequenced explicitly so that every compiler (and the Rust verifier) agrees.
+    uint64_t hi = rng.generate();
+    uint64_t lo = rng.generate();
+    uint64_t val = hi << 32 | lo;
     while (val + P < val) { // If we wrap after adding P, we are in the final copy of P
       val = rng.generate(); // Try again.
     }
@@ -62,7 +62,12 @@ struct Fp4 {
 
   /// Generate a random field element uniformly
   template <typename Rng> static Fp4 random(DEVADDR Rng& rng) {
-    return Fp4(Fp::random(rng), Fp::random(rng), Fp::random(rng), Fp::random(rng));
+    // Argument evaluation order is unspecified, so draw each element in turn.
+    Fp a = Fp::random(rng);
+    Fp b = Fp::random(rng);
+    Fp c = Fp::random(rng);
+    Fp d = Fp::random(rng);
+    return Fp4(a, b, c, d);
   }
 
   // Implement the addition/subtraction overloads
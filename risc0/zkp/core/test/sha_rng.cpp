@@ -0,0 +1,32 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "risc0/zkp/core/sha_rng.h"
+#include "risc0/zkp/core/fp4.h"
+
+#include <gtest/gtest.h>
+
+namespace risc0 {
+
+TEST(ShaRng, FieldDraws) {
+  // Challenges drawn by the prover must not depend on the compiler, and any
+  // verifier written in another language has to draw the same ones.
+  ShaRng rng;
+  rng.mix(shaHash("abc"));
+  EXPECT_EQ(Fp::random(rng), Fp(963407152));
+  EXPECT_EQ(Fp::random(rng), Fp(35496));
+  EXPECT_EQ(Fp4::random(rng), Fp4(244757508, 736377549, 1580838935, 1105023166));
+}
+
+} // namespace risc0
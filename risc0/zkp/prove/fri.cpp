@@ -59,10 +59,16 @@ struct ProveRoundInfo {
 
 } // namespace
 
-void friProve(WriteIOP& iop, AccelConstSlice<Fp> coeffs, InnerProve inner) {
+void friProve(WriteIOP& iop,
+              AccelConstSlice<Fp> coeffs,
+              InnerProve inner,
+              CheckCancelled checkCancelled) {
   size_t origDomain = coeffs.size() / 4 * kInvRate;
   std::vector<ProveRoundInfo> rounds;
   while (coeffs.size() / 4 > kFriMinDegree) {
+    if (checkCancelled) {
+      checkCancelled();
+    }
     rounds.emplace_back(iop, coeffs);
     coeffs = rounds.back().outCoeffs;
   }
@@ -78,6 +84,9 @@ void friProve(WriteIOP& iop, AccelConstSlice<Fp> coeffs, InnerProve inner) {
     iop.commit(digest);
   }
   // Do queries
+  if (checkCancelled) {
+    checkCancelled();
+  }
   LOG(1, "Doing Queries");
   for (size_t q = 0; q < kQueries; q++) {
     // Get a 'random' index.
@@ -23,11 +23,16 @@
 namespace risc0 {
 
 using InnerProve = std::function<void(WriteIOP& iop, size_t idx)>;
+using CheckCancelled = std::function<void()>;
 
 // Make a proof that poly has degree <= poly.size().
 // Additionally, for each query, any innner proof data can we written (which for verifications
 // will provide the validation that the point queried is correct).  Note, evaluation is over a
-// region kInvRate * poly.size().  Finally, this code may modify the coeffs sent in.
-void friProve(WriteIOP& iop, AccelConstSlice<Fp> coeffs, InnerProve inner);
+// region kInvRate * poly.size().  Finally, this code may modify the coeffs sent in.  If given,
+// checkCancelled is called before each round and before the queries, and may throw to stop.
+void friProve(WriteIOP& iop,
+              AccelConstSlice<Fp> coeffs,
+              InnerProve inner,
+              CheckCancelled checkCancelled = nullptr);
 
 } // namespace risc0
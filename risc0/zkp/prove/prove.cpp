@@ -48,6 +48,7 @@ std::vector<uint32_t> prove(ProveCircuit& circuit) {
 
   // Do main execution + get size
   circuit.execute(iop);
+  circuit.checkCancelled();
   uint32_t po2 = circuit.getPo2();
   REQUIRE(po2 <= kMaxCyclesPo2);
   size_t size = size_t(1) << po2;
@@ -67,6 +68,7 @@ std::vector<uint32_t> prove(ProveCircuit& circuit) {
   LOG(1, "codeGroup: " << codeGroup.getMerkle().getRoot());
   LOG(1, "dataGroup: " << dataGroup.getMerkle().getRoot());
 
+  circuit.checkCancelled();
   circuit.accumulate(iop);
 
   // Make the accum group + commit
@@ -80,6 +82,7 @@ std::vector<uint32_t> prove(ProveCircuit& circuit) {
   Fp4 polyMix = Fp4::random(iop);
 
   // Now generate the check polynomial
+  circuit.checkCancelled();
   size_t domain = size * kInvRate;
   auto checkPoly = AccelSlice<Fp>::allocate(4 * domain);
   circuit.evalCheck(checkPoly,
@@ -119,6 +122,7 @@ std::vector<uint32_t> prove(ProveCircuit& circuit) {
   // actually doing anything.
 
   // Make the PolyGroup + add it to the IOP;
+  circuit.checkCancelled();
   PolyGroup checkGroup(checkPoly, kCheckSize, size);
   checkGroup.getMerkle().commit(iop);
   LOG(1, "checkGroup: " << checkGroup.getMerkle().getRoot());
@@ -204,6 +208,7 @@ std::vector<uint32_t> prove(ProveCircuit& circuit) {
   LOG(1, "Mix = " << mix);
 
   // Do the coefficent mixing
+  circuit.checkCancelled();
   // Begin by making a zeroed output buffer
   auto combos = AccelSlice<Fp4>::copy(std::vector<Fp4>(size * (comboCount + 1)));
   Fp4 curMix(1);
@@ -267,12 +272,16 @@ std::vector<uint32_t> prove(ProveCircuit& circuit) {
   // Finally do the FRI protocol to prove the degree of the polynomial
   batchBitReverse(finalPolyCoeffs, 4);
   LOG(1, "FRI-proof, size = " << finalPolyCoeffs.size() / 4);
-  friProve(iop, finalPolyCoeffs, [&](WriteIOP& iop, size_t idx) {
-    accumGroup.getMerkle().prove(iop, idx);
-    codeGroup.getMerkle().prove(iop, idx);
-    dataGroup.getMerkle().prove(iop, idx);
-    checkGroup.getMerkle().prove(iop, idx);
-  });
+  friProve(
+      iop,
+      finalPolyCoeffs,
+      [&](WriteIOP& iop, size_t idx) {
+        accumGroup.getMerkle().prove(iop, idx);
+        codeGroup.getMerkle().prove(iop, idx);
+        dataGroup.getMerkle().prove(iop, idx);
+        checkGroup.getMerkle().prove(iop, idx);
+      },
+      [&]() { circuit.checkCancelled(); });
 
   // Return final proof
   std::vector<uint32_t> ret = iop.getProof();
@@ -52,6 +52,8 @@ public:
   virtual const std::vector<Fp>& getData() const = 0;
   // Accum is only available after accumulate
   virtual const std::vector<Fp>& getAccum() const = 0;
+  // Called between the phases of the proof, including each round of FRI; throw to abandon it.
+  virtual void checkCancelled() const {}
 };
 
 std::vector<uint32_t> prove(ProveCircuit& circuit);
@@ -0,0 +1,80 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Translates the polynomial check and tap set from step.cpp.inc into Rust tables for the
+// zkvm_verify crate, so that the Rust verifier always matches the circuit the prover was built
+// with.
+
+#include <cstddef>
+#include <fstream>
+#include <iostream>
+
+namespace {
+
+#define SIZES
+#include "risc0/zkvm/circuit/step.cpp.inc"
+#undef SIZES
+
+void writeSteps(std::ostream& os) {
+  os << "pub(crate) const STEPS: &[Op] = &[\n";
+  size_t result = 0;
+#define CHECK_EVAL
+#define do_const(out, cval) os << "    Op::Const(" << out << ", " << cval << "),\n";
+#define do_get(out, buf, reg, back, id) os << "    Op::Get(" << out << ", " << id << "),\n";
+#define do_get_global(out, reg) os << "    Op::GetGlobal(" << out << ", " << reg << "),\n";
+#define do_begin(out) os << "    Op::Begin(" << out << "),\n";
+#define do_assert_zero(out, in, zval, loc)                                                         \
+  os << "    Op::AssertZero(" << out << ", " << in << ", " << zval << "),\n";
+#define do_combine(out, prev, cond, inner, loc)                                                    \
+  os << "    Op::Combine(" << out << ", " << prev << ", " << cond << ", " << inner << "),\n";
+#define do_add(out, a, b) os << "    Op::Add(" << out << ", " << a << ", " << b << "),\n";
+#define do_sub(out, a, b) os << "    Op::Sub(" << out << ", " << a << ", " << b << "),\n";
+#define do_mul(out, a, b) os << "    Op::Mul(" << out << ", " << a << ", " << b << "),\n";
+#define do_result(out) result = out;
+#include "risc0/zkvm/circuit/step.cpp.inc"
+#undef CHECK_EVAL
+  os << "];\n\n";
+  os << "pub(crate) const RESULT: usize = " << result << ";\n\n";
+}
+
+void writeTaps(std::ostream& os) {
+  const char* accum = "Accum";
+  const char* code = "Code";
+  const char* data = "Data";
+  os << "pub(crate) const TAPS: &[(RegisterGroup, usize, usize)] = &[\n";
+#define tap(base, offset, back)                                                                    \
+  os << "    (RegisterGroup::" << base << ", " << offset << ", " << back << "),\n";
+#define TAPS
+#include "risc0/zkvm/circuit/step.cpp.inc"
+#undef TAPS
+  os << "];\n";
+}
+
+} // namespace
+
+int main(int argc, char* argv[]) {
+  if (argc < 2) {
+    std::cerr << "usage: make-rust-circuit <output_path>" << std::endl;
+    return 1;
+  }
+
+  std::ofstream fout(argv[1]);
+  fout << "// Generated by //risc0/zkvm/circuit/make-rust-circuit, do not edit.\n\n";
+  fout << "use crate::{circuit::Op, taps::RegisterGroup};\n\n";
+  fout << "pub(crate) const NUM_STEP_FP4S: usize = " << kNumStepFp4s << ";\n";
+  fout << "pub(crate) const NUM_STEP_CONS: usize = " << kNumStepCons << ";\n\n";
+  writeSteps(fout);
+  writeTaps(fout);
+  return 0;
+}
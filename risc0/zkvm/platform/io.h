@@ -16,12 +16,20 @@
 
 namespace risc0 {
 
+// Must match sdk/rust/platform/src/io.rs
 constexpr size_t kGPIO_SHA = 0x001C0000;
 constexpr size_t kGPIO_Write = 0x001C0004;
 constexpr size_t kGPIO_Commit = 0x001C0008;
 constexpr size_t kGPIO_Fault = 0x001C000C;
 constexpr size_t kGPIO_Log = 0x001C0010;
 constexpr size_t kGPIO_GetKey = 0x001C0014;
+constexpr size_t kGPIO_GetLogLevel = 0x001C0018;
+constexpr size_t kGPIO_GetCycle = 0x001C001C;
+constexpr size_t kGPIO_Span = 0x001C0020;
+
+// Scratch space in the GPIO region where the Rust guest builds the descriptor it passes to one of
+// the ports above.
+constexpr size_t kGPIO_Desc = 0x001D0000;
 
 struct ShaDescriptor;
 
@@ -31,7 +39,13 @@ struct IoDescriptor {
 };
 
 struct FaultDescriptor {
+  // The NUL-terminated failure message.
   uint32_t addr;
+  // The NUL-terminated source file that failed, or 0 if unknown, in which case line and column are
+  // ignored.
+  uint32_t file;
+  uint32_t line;
+  uint32_t column;
 };
 
 struct LogDescriptor {
@@ -44,6 +58,23 @@ struct GetKeyDescriptor {
   uint32_t mode;
 };
 
+struct GetLogLevelDescriptor {
+  // Where the host stores the maximum level of guest log records, see kGuestLogInfo.
+  uint32_t addr;
+};
+
+struct GetCycleDescriptor {
+  // Where the host stores the cycle on which the descriptor was written.
+  uint32_t addr;
+};
+
+struct SpanDescriptor {
+  uint32_t name;
+  uint32_t len;
+  // Non-zero when entering the span, zero when exiting it.
+  uint32_t enter;
+};
+
 inline volatile ShaDescriptor* volatile* GPIO_SHA() {
   return reinterpret_cast<volatile ShaDescriptor* volatile*>(kGPIO_SHA);
 }
@@ -68,4 +99,16 @@ inline volatile GetKeyDescriptor* volatile* GPIO_GetKey() {
   return reinterpret_cast<volatile GetKeyDescriptor* volatile*>(kGPIO_GetKey);
 }
 
+inline volatile GetLogLevelDescriptor* volatile* GPIO_GetLogLevel() {
+  return reinterpret_cast<volatile GetLogLevelDescriptor* volatile*>(kGPIO_GetLogLevel);
+}
+
+inline volatile GetCycleDescriptor* volatile* GPIO_GetCycle() {
+  return reinterpret_cast<volatile GetCycleDescriptor* volatile*>(kGPIO_GetCycle);
+}
+
+inline volatile SpanDescriptor* volatile* GPIO_Span() {
+  return reinterpret_cast<volatile SpanDescriptor* volatile*>(kGPIO_Span);
+}
+
 } // namespace risc0
@@ -31,7 +31,9 @@ constexpr size_t k256KB = 0x00040000;
 constexpr size_t k512KB = 0x00080000;
 constexpr size_t k1MB = 0x00100000;
 
-// Must match riscv.ld
+// Must match risc0.ld and sdk/rust/platform/src/memory.rs. Methods can resize the stack, heap, input,
+// output and commit regions when they are linked, so these are only their defaults, see
+// risc0/zkvm/prove/layout.h.
 // clang-format off
 MEM_REGION(Stack,  0x00000000, k256KB)
 MEM_REGION(Data,   0x00040000, k256KB)
@@ -18,11 +18,38 @@
 #include "risc0/core/log.h"
 #include "risc0/core/util.h"
 #include "risc0/zkvm/circuit/constants.h"
+#include "risc0/zkvm/platform/memory.h"
+
+#include <sstream>
 
 namespace risc0 {
 
-ExecState::ExecState(const std::string& elfFile) {
-  startAddr = loadElf(elfFile, kMemSize, image);
+namespace {
+
+// How often, in cycles, to poll the MemoryHandler for cancellation.
+constexpr size_t kCancelInterval = 1 << 16;
+
+// The most frames ExecState::getCallStack returns.
+constexpr size_t kMaxCallDepth = 256;
+
+std::string cycleLimitMessage(size_t limit) {
+  std::stringstream ss;
+  ss << "Guest exceeded the cycle limit of " << limit << " cycles";
+  return ss.str();
+}
+
+} // namespace
+
+CycleLimitExceeded::CycleLimitExceeded(size_t limit)
+    : std::runtime_error(cycleLimitMessage(limit)), limit(limit) {}
+
+Cancelled::Cancelled() : std::runtime_error("Proof was cancelled") {}
+
+ExecState::ExecState(const std::string& elfFile) : ExecState(readElf(elfFile)) {}
+
+ExecState::ExecState(const std::vector<uint8_t>& elf) {
+  startAddr = loadElf(elf, kMemSize, image);
+  stack = loadMemoryLayout(elf).stack;
 }
 
 void ExecState::run(size_t maxSteps, MemoryHandler& io) {
@@ -38,10 +65,14 @@ void ExecState::init(size_t maxSteps, MemoryHandler& io) {
   context.mem.data[0] = 0;
   context.numSteps = nearestPo2(image.size() + 3 + kZkCycles);
   if (context.numSteps > maxSteps) {
-    throw std::runtime_error("Elf too large to fix in maxSteps");
+    // Loading the image alone takes more cycles than the limit allows.
+    throw CycleLimitExceeded(maxSteps);
   }
   this->maxSteps = maxSteps;
   done = false;
+  sampleInterval = io.getSampleInterval();
+  nextSample = sampleInterval;
+  minStackPointer = stack.end;
 
   LOG(1, "image.size() = " << image.size());
   LOG(1, "numSteps = " << context.numSteps);
@@ -74,6 +105,13 @@ bool ExecState::step() {
   if (done) {
     return false;
   }
+  if (context.curStep >= maxSteps) {
+    throw CycleLimitExceeded(maxSteps);
+  }
+  // curStep advances by 3 per call, so this fires once per interval
+  if (context.curStep % kCancelInterval < 3 && context.io->isCancelled()) {
+    throw Cancelled();
+  }
   // If we are about to hit the end, expand
   if (context.curStep + 3 >= (context.numSteps - 1 - kZkCycles)) {
     expand();
@@ -89,8 +127,30 @@ bool ExecState::step() {
   // If it's not, normal execution is complete
   if (!isFinal) {
     done = true;
+    return false;
+  }
+  checkStack();
+  if (sampleInterval && context.curStep >= nextSample) {
+    context.io->onSample(getCallStack());
+    nextSample = context.curStep + sampleInterval;
+  }
+  return true;
+}
+
+void ExecState::checkStack() {
+  uint32_t sp = getRegister(2);
+  // The stack pointer is zero until crt0 points it at the top of the stack.
+  if (sp == 0) {
+    return;
+  }
+  // The stack grows down towards address 0, so an overflow wraps around to the top of memory.
+  if (sp < stack.start || sp > stack.end) {
+    throw GuestFault("Guest stack overflow");
+  }
+  if (sp < minStackPointer) {
+    minStackPointer = sp;
+    context.io->onStackUsage(stack.end - sp);
   }
-  return !done;
 }
 
 void ExecState::fini() {
@@ -126,21 +186,47 @@ uint32_t ExecState::getPC() {
 
 std::vector<uint32_t> ExecState::getRegisters() {
   std::vector<uint32_t> out;
-  size_t offset = 30;
   for (size_t i = 0; i < 32; i++) {
-    uint32_t low =
-        data.data()[(i * 2 + offset) * context.numSteps + context.curStep - 1].asUInt32();
-    uint32_t high =
-        data.data()[(i * 2 + offset + 1) * context.numSteps + context.curStep - 1].asUInt32();
-    REQUIRE(low < 65536 && high < 65536);
-    out.push_back(low | (high << 16));
+    out.push_back(getRegister(i));
   }
   return out;
 }
 
+uint32_t ExecState::getRegister(size_t idx) {
+  size_t offset = 30;
+  uint32_t low =
+      data.data()[(idx * 2 + offset) * context.numSteps + context.curStep - 1].asUInt32();
+  uint32_t high =
+      data.data()[(idx * 2 + offset + 1) * context.numSteps + context.curStep - 1].asUInt32();
+  REQUIRE(low < 65536 && high < 65536);
+  return low | (high << 16);
+}
+
+std::vector<uint32_t> ExecState::getCallStack() {
+  std::vector<uint32_t> frames = {getPC()};
+  // Each frame stores the return address and the caller's frame pointer just below the address its
+  // frame pointer (x8) holds. Stop at anything that does not look like a frame on the stack.
+  uint32_t fp = getRegister(8);
+  while (frames.size() < kMaxCallDepth && fp >= stack.start + 8 && fp <= stack.end &&
+         fp % 4 == 0) {
+    auto ra = context.mem.data.find((fp - 4) / 4);
+    auto prevFp = context.mem.data.find((fp - 8) / 4);
+    if (ra == context.mem.data.end() || prevFp == context.mem.data.end()) {
+      break;
+    }
+    frames.push_back(ra->second);
+    // Callers' frames are further up the stack.
+    if (prevFp->second <= fp) {
+      break;
+    }
+    fp = prevFp->second;
+  }
+  return frames;
+}
+
 void ExecState::expand() {
-  if (context.numSteps == maxSteps) {
-    throw std::runtime_error("Expand failed: at max steps");
+  if (context.numSteps >= maxSteps) {
+    throw CycleLimitExceeded(maxSteps);
   }
   LOG(1, "EXPANDING!");
   {
@@ -14,16 +14,36 @@
 
 #pragma once
 
+#include "risc0/zkvm/prove/layout.h"
 #include "risc0/zkvm/prove/step.h"
 
+#include <stdexcept>
 #include <string>
 #include <vector>
 
 namespace risc0 {
 
+// Thrown when the guest does not finish within the cycle limit passed to ExecState::run, or when
+// its image is too large to even be loaded within it.
+class CycleLimitExceeded : public std::runtime_error {
+public:
+  CycleLimitExceeded(size_t limit);
+
+  size_t limit;
+};
+
+// Thrown when the MemoryHandler reports that the proof has been cancelled.
+class Cancelled : public std::runtime_error {
+public:
+  Cancelled();
+};
+
 struct ExecState {
   ExecState(const std::string& elfFile);
-  // A helper function to call init, step*, fini
+  // Loads the guest from an ELF image that is already in memory.
+  ExecState(const std::vector<uint8_t>& elf);
+  // A helper function to call init, step*, fini.
+  // Execution is limited to 'maxSteps' cycles, which need not be a power of 2.
   void run(size_t maxSteps, MemoryHandler& io);
 
   // Setup VM and run until 'reset' cycle
@@ -37,16 +57,31 @@ struct ExecState {
   uint32_t getPC();
   // Get the state of all 32 registers
   std::vector<uint32_t> getRegisters();
+  // Get the state of register 'idx'
+  uint32_t getRegister(size_t idx);
+  // Get the PC followed by the return address of each frame on the stack, found by following the
+  // frame pointer chain. Only the PC is reliable unless the guest was built with frame pointers.
+  std::vector<uint32_t> getCallStack();
+
+  // Throws GuestFault if the stack pointer left the stack, and reports each new high-water mark.
+  void checkStack();
 
   void expand();
 
   uint32_t startAddr;
   std::map<uint32_t, uint32_t> image;
+  // Where the stack pointer may point, from the guest's memory layout.
+  MemoryRegion stack;
+  // The lowest stack pointer seen so far, which marks the most stack the guest has used.
+  uint32_t minStackPointer;
   StepContext context;
   std::vector<Fp> code;
   std::vector<Fp> data;
   size_t maxSteps;
   bool done;
+  // When sampling the call stack, how often and on which cycle to take the next sample.
+  size_t sampleInterval;
+  size_t nextSample;
 };
 
 } // namespace risc0
@@ -45,8 +45,45 @@ static void processSHA(MemoryState& mem, const ShaDescriptor& desc) {
   }
 }
 
-void IoHandler::onFault(const std::string& msg) {
-  throw std::runtime_error(msg);
+namespace {
+
+std::string faultMessage(const std::string& msg,
+                         const std::string& file,
+                         uint32_t line,
+                         uint32_t column) {
+  if (file.empty()) {
+    return msg;
+  }
+  std::stringstream ss;
+  ss << "Guest panicked at " << file << ":" << line << ":" << column << ": " << msg;
+  return ss.str();
+}
+
+std::string loadString(MemoryState& mem, uint32_t addr) {
+  size_t len = mem.strlen(addr);
+  std::vector<char> buf(len);
+  mem.loadRegion(addr, buf.data(), len);
+  return std::string(buf.data(), buf.size());
+}
+
+} // namespace
+
+GuestFault::GuestFault(const std::string& msg,
+                       const std::string& file,
+                       uint32_t line,
+                       uint32_t column)
+    : std::runtime_error(faultMessage(msg, file, line, column))
+    , msg(msg)
+    , file(file)
+    , line(line)
+    , column(column) {}
+
+void IoHandler::onFault(const GuestFault& fault) {
+  throw fault;
+}
+
+void IoHandler::onLog(uint32_t cycle, const std::string& msg) {
+  LOG(0, "R0VM[C" << cycle << "]> " << msg);
 }
 
 MemoryHandler::MemoryHandler() : io(nullptr) {}
@@ -59,6 +96,26 @@ void MemoryHandler::onInit(MemoryState& mem) {
   }
 }
 
+bool MemoryHandler::isCancelled() {
+  return io && io->isCancelled();
+}
+
+uint32_t MemoryHandler::getSampleInterval() {
+  return io ? io->getSampleInterval() : 0;
+}
+
+void MemoryHandler::onSample(const std::vector<uint32_t>& stack) {
+  if (io) {
+    io->onSample(stack);
+  }
+}
+
+void MemoryHandler::onStackUsage(uint32_t bytes) {
+  if (io) {
+    io->onStackUsage(bytes);
+  }
+}
+
 void MemoryHandler::onWrite(MemoryState& mem, uint32_t cycle, uint32_t addr, uint32_t value) {
   LOG(2, "MemoryHandler::onWrite> " << hex(addr) << ": " << hex(value));
   switch (addr) {
@@ -93,11 +150,12 @@ void MemoryHandler::onWrite(MemoryState& mem, uint32_t cycle, uint32_t addr, uin
     FaultDescriptor desc;
     mem.loadRegion(value, &desc, sizeof(desc));
     if (io) {
-      size_t len = mem.strlen(desc.addr);
-      std::vector<char> buf(len);
-      mem.loadRegion(desc.addr, buf.data(), len);
-      std::string str(buf.data(), buf.size());
-      io->onFault(str);
+      std::string msg = loadString(mem, desc.addr);
+      if (desc.file) {
+        io->onFault(GuestFault(msg, loadString(mem, desc.file), desc.line, desc.column));
+      } else {
+        io->onFault(GuestFault(msg));
+      }
     }
   } break;
   case kGPIO_Log: {
@@ -108,7 +166,11 @@ void MemoryHandler::onWrite(MemoryState& mem, uint32_t cycle, uint32_t addr, uin
     std::vector<char> buf(len);
     mem.loadRegion(desc.addr, buf.data(), len);
     std::string str(buf.data(), buf.size());
-    LOG(0, "R0VM[C" << cycle << "]> " << str);
+    if (io) {
+      io->onLog(cycle, str);
+    } else {
+      LOG(0, "R0VM[C" << cycle << "]> " << str);
+    }
   } break;
   case kGPIO_GetKey: {
     LOG(1, "MemoryHandler::onWrite> GPIO_GetKey");
@@ -134,6 +196,38 @@ void MemoryHandler::onWrite(MemoryState& mem, uint32_t cycle, uint32_t addr, uin
     const Key& key = store[str];
     mem.store(desc.addr, reinterpret_cast<const uint8_t*>(&key), sizeof(Key));
   } break;
+  case kGPIO_GetLogLevel: {
+    LOG(1, "MemoryHandler::onWrite> GPIO_GetLogLevel");
+    GetLogLevelDescriptor desc;
+    mem.loadRegion(value, &desc, sizeof(desc));
+    uint32_t level = io ? io->getGuestLogLevel() : kGuestLogInfo;
+    LOG(1, "  level = " << level);
+    mem.store(desc.addr, level);
+  } break;
+  case kGPIO_GetCycle: {
+    LOG(2, "MemoryHandler::onWrite> GPIO_GetCycle");
+    GetCycleDescriptor desc;
+    mem.loadRegion(value, &desc, sizeof(desc));
+    mem.store(desc.addr, cycle);
+  } break;
+  case kGPIO_Span: {
+    LOG(2, "MemoryHandler::onWrite> GPIO_Span");
+    SpanDescriptor desc;
+    mem.loadRegion(value, &desc, sizeof(desc));
+    // The length comes from the guest, so check it before allocating for it.
+    if (desc.name >= kMemSize || desc.len > kMemSize - desc.name) {
+      std::stringstream ss;
+      ss << "span name out of range: " << hex(desc.name) << ", len: " << desc.len;
+      throw std::out_of_range(ss.str());
+    }
+    std::vector<char> buf(desc.len);
+    mem.loadRegion(desc.name, buf.data(), desc.len);
+    std::string name(buf.data(), buf.size());
+    LOG(2, "  name = " << name << ", enter = " << desc.enter);
+    if (io) {
+      io->onSpan(cycle, name, desc.enter != 0);
+    }
+  } break;
   }
 }
 
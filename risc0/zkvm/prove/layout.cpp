@@ -0,0 +1,72 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "risc0/zkvm/prove/layout.h"
+
+#include "risc0/core/elf.h"
+#include "risc0/zkvm/platform/memory.h"
+
+#include <cstring>
+#include <sstream>
+#include <stdexcept>
+#include <string>
+
+namespace risc0 {
+
+namespace {
+
+// Checks that 'region' is word aligned and lies within [lower, upper).
+void checkRegion(const std::string& name, MemoryRegion region, uint32_t lower, uint32_t upper) {
+  if (region.start % 4 != 0 || region.end % 4 != 0 || region.start > region.end ||
+      region.start < lower || region.end > upper) {
+    std::stringstream ss;
+    ss << "Invalid memory layout: the " << name << " region 0x" << std::hex << region.start
+       << "-0x" << region.end << " does not fit in 0x" << lower << "-0x" << upper;
+    throw std::runtime_error(ss.str());
+  }
+}
+
+} // namespace
+
+MemoryLayout defaultMemoryLayout() {
+  return MemoryLayout{
+      {kMemStackStart, kMemStackEnd},
+      {kMemHeapStart, kMemHeapEnd},
+      {kMemInputStart, kMemInputEnd},
+      {kMemOutputStart, kMemOutputEnd},
+      {kMemCommitStart, kMemCommitEnd},
+  };
+}
+
+MemoryLayout loadMemoryLayout(const std::vector<uint8_t>& elf) {
+  std::vector<uint8_t> section = loadElfSection(elf, ".risc0.layout");
+  if (section.empty()) {
+    // Linked before regions could be resized.
+    return defaultMemoryLayout();
+  }
+  MemoryLayout layout;
+  if (section.size() != sizeof(layout)) {
+    throw std::runtime_error("Invalid memory layout: the .risc0.layout section has the wrong size");
+  }
+  std::memcpy(&layout, section.data(), sizeof(layout));
+  // Each region must follow the previous one, in the order risc0.ld lays them out.
+  checkRegion("stack", layout.stack, 0, kMemGPIOStart);
+  checkRegion("heap", layout.heap, layout.stack.end, kMemGPIOStart);
+  checkRegion("input", layout.input, layout.heap.end, kMemGPIOStart);
+  checkRegion("output", layout.output, kMemWOMStart, kMemSize);
+  checkRegion("commit", layout.commit, layout.output.end, kMemSize);
+  return layout;
+}
+
+} // namespace risc0
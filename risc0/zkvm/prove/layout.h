@@ -0,0 +1,46 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include <cstdint>
+#include <vector>
+
+namespace risc0 {
+
+// A range of guest memory, from start up to but excluding end.
+struct MemoryRegion {
+  uint32_t start;
+  uint32_t end;
+};
+
+// The regions of guest memory that a method can resize when it is linked, see risc0.ld. The
+// .risc0.layout section holds the bounds of each region in this order.
+struct MemoryLayout {
+  MemoryRegion stack;
+  MemoryRegion heap;
+  MemoryRegion input;
+  MemoryRegion output;
+  MemoryRegion commit;
+};
+
+// The layout of methods that do not resize any region, from memory.h.
+MemoryLayout defaultMemoryLayout();
+
+// Reads the layout from the .risc0.layout section that risc0.ld emits, or returns the default
+// layout if the ELF image was linked before there was one. Throws std::runtime_error if the
+// regions overlap or lie outside of the parts of the memory map they belong to.
+MemoryLayout loadMemoryLayout(const std::vector<uint8_t>& elf);
+
+} // namespace risc0
@@ -19,15 +19,14 @@
 
 #include "risc0/core/elf.h"
 #include "risc0/zkp/prove/poly_group.h"
+#include "risc0/zkvm/prove/layout.h"
 #include "risc0/zkvm/prove/step.h"
 
 namespace risc0 {
 
-MethodID makeMethodID(const std::string& elfFile) {
-  // Load the ELF file into an image
-  std::map<uint32_t, uint32_t> image;
-  uint32_t startAddr = loadElf(elfFile, kMemSize, image);
+namespace {
 
+MethodID makeMethodID(uint32_t startAddr, const std::map<uint32_t, uint32_t>& image) {
   // Start with an empty return value
   MethodID ret;
 
@@ -54,6 +53,21 @@ MethodID makeMethodID(const std::string& elfFile) {
   return ret;
 }
 
+} // namespace
+
+MethodID makeMethodID(const std::string& elfFile) {
+  return makeMethodID(readElf(elfFile));
+}
+
+MethodID makeMethodID(const std::vector<uint8_t>& elf) {
+  // Rejects a method whose regions do not fit when it is built, rather than when it is run.
+  loadMemoryLayout(elf);
+  // Load the ELF file into an image
+  std::map<uint32_t, uint32_t> image;
+  uint32_t startAddr = loadElf(elf, kMemSize, image);
+  return makeMethodID(startAddr, image);
+}
+
 void writeMethodID(const std::string& filename, const MethodID& id) {
   std::ofstream file(filename, std::ios::out | std::ios::binary);
   if (!file) {
@@ -16,9 +16,12 @@
 
 #include "risc0/zkvm/verify/method_id.h"
 
+#include <vector>
+
 namespace risc0 {
 
 MethodID makeMethodID(const std::string& elfFile);
+MethodID makeMethodID(const std::vector<uint8_t>& elf);
 void writeMethodID(const std::string& filename, const MethodID& id);
 
 } // namespace risc0
@@ -19,6 +19,8 @@
 #include "risc0/zkvm/prove/exec.h"
 #include "risc0/zkvm/verify/riscv.h"
 
+#include <algorithm>
+
 #include "oneapi/tbb/parallel_for.h"
 using oneapi::tbb::parallel_for;
 
@@ -28,7 +30,7 @@ namespace {
 
 class RiscVProveCircuit : public ProveCircuit {
 public:
-  RiscVProveCircuit(const std::string& elfFile, MemoryHandler& io);
+  RiscVProveCircuit(ExecState exec, MemoryHandler& io, size_t maxCycles);
   TapSetRef getTaps() const override { return getRiscVTaps(); }
   void execute(WriteIOP& iop) override;
   void accumulate(WriteIOP& iop) override;
@@ -45,22 +47,24 @@ public:
   const std::vector<Fp>& getCode() const override { return exec_.code; }
   const std::vector<Fp>& getData() const override { return exec_.data; }
   const std::vector<Fp>& getAccum() const override { return accum_; }
+  void checkCancelled() const override;
 
 private:
   ExecState exec_;
   std::vector<Fp> accum_;
   MemoryHandler& io_;
+  size_t maxCycles_;
   uint32_t po2_;
 };
 
 } // namespace
 
-RiscVProveCircuit::RiscVProveCircuit(const std::string& elfFile, MemoryHandler& io)
-    : exec_(elfFile), io_(io) {}
+RiscVProveCircuit::RiscVProveCircuit(ExecState exec, MemoryHandler& io, size_t maxCycles)
+    : exec_(std::move(exec)), io_(io), maxCycles_(std::min(maxCycles, kMaxCycles)) {}
 
 void RiscVProveCircuit::execute(WriteIOP& iop) {
   // Run actual RISC-V execution
-  exec_.run(kMaxCycles, io_);
+  exec_.run(maxCycles_, io_);
 
   // Get # of timesteps
   po2_ = log2Ceil(exec_.context.numSteps);
@@ -103,6 +107,12 @@ void RiscVProveCircuit::execute(WriteIOP& iop) {
 #endif
 }
 
+void RiscVProveCircuit::checkCancelled() const {
+  if (io_.isCancelled()) {
+    throw Cancelled();
+  }
+}
+
 void RiscVProveCircuit::accumulate(WriteIOP& iop) {
   size_t size = size_t(1) << po2_;
   LOG(1, "size = " << size);
@@ -207,8 +217,16 @@ void RiscVProveCircuit::evalCheck( //
 
 using oneapi::tbb::parallel_for;
 
-std::unique_ptr<ProveCircuit> getRiscVProveCircuit(const std::string& elfFile, MemoryHandler& io) {
-  return std::make_unique<RiscVProveCircuit>(elfFile, io);
+std::unique_ptr<ProveCircuit> getRiscVProveCircuit(const std::string& elfFile,
+                                                   MemoryHandler& io,
+                                                   size_t maxCycles) {
+  return std::make_unique<RiscVProveCircuit>(ExecState(elfFile), io, maxCycles);
+}
+
+std::unique_ptr<ProveCircuit> getRiscVProveCircuit(const std::vector<uint8_t>& elf,
+                                                   MemoryHandler& io,
+                                                   size_t maxCycles) {
+  return std::make_unique<RiscVProveCircuit>(ExecState(elf), io, maxCycles);
 }
 
 } // namespace risc0
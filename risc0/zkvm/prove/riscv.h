@@ -21,6 +21,14 @@
 
 namespace risc0 {
 
-std::unique_ptr<ProveCircuit> getRiscVProveCircuit(const std::string& elfFile, MemoryHandler& io);
+// Execution is limited to 'maxCycles', which is clamped to kMaxCycles.
+std::unique_ptr<ProveCircuit> getRiscVProveCircuit(const std::string& elfFile,
+                                                   MemoryHandler& io,
+                                                   size_t maxCycles = kMaxCycles);
+
+// As above, but loads the guest from an ELF image that is already in memory.
+std::unique_ptr<ProveCircuit> getRiscVProveCircuit(const std::vector<uint8_t>& elf,
+                                                   MemoryHandler& io,
+                                                   size_t maxCycles = kMaxCycles);
 
 } // namespace risc0
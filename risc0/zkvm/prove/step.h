@@ -20,6 +20,8 @@
 
 #include <map>
 #include <set>
+#include <stdexcept>
+#include <string>
 #include <vector>
 
 namespace risc0 {
@@ -58,11 +60,50 @@ struct MemoryState {
   size_t strlen(uint32_t addr);
 };
 
+// Thrown by the default IoHandler::onFault when the guest reports a failure, either a panic or
+// an error returned from its entry point, and by ExecState when the guest overflows its stack.
+class GuestFault : public std::runtime_error {
+public:
+  GuestFault(const std::string& msg,
+             const std::string& file = "",
+             uint32_t line = 0,
+             uint32_t column = 0);
+
+  // The message reported by the guest, without the location.
+  std::string msg;
+  // The source file that failed, or empty if the guest did not report a location.
+  std::string file;
+  uint32_t line;
+  uint32_t column;
+};
+
+// Maximum levels of guest log records, matching the order of the Rust `log` crate's LevelFilter.
+constexpr uint32_t kGuestLogOff = 0;
+constexpr uint32_t kGuestLogError = 1;
+constexpr uint32_t kGuestLogWarn = 2;
+constexpr uint32_t kGuestLogInfo = 3;
+constexpr uint32_t kGuestLogDebug = 4;
+constexpr uint32_t kGuestLogTrace = 5;
+
 struct IoHandler {
   virtual void onInit(MemoryState& mem) {}
   virtual void onWrite(const BufferU8& data) {}
   virtual void onCommit(const BufferU8& data) {}
-  virtual void onFault(const std::string& msg);
+  virtual void onFault(const GuestFault& fault);
+  virtual void onLog(uint32_t cycle, const std::string& msg);
+  // The maximum level of log records the guest should emit.
+  virtual uint32_t getGuestLogLevel() { return kGuestLogInfo; }
+  // Called when the guest enters or exits a profiling span.
+  virtual void onSpan(uint32_t cycle, const std::string& name, bool enter) {}
+  // How often, in cycles, to sample the guest call stack, or 0 to not sample it.
+  virtual uint32_t getSampleInterval() { return 0; }
+  // Called with each sampled call stack, innermost frame first, see ExecState::getCallStack.
+  virtual void onSample(const std::vector<uint32_t>& stack) {}
+  // Called whenever the guest uses more of its stack than before, with the bytes in use.
+  virtual void onStackUsage(uint32_t bytes) {}
+  // Polled periodically while proving; returning true aborts the proof by throwing Cancelled.
+  // This may be the only method called from a thread other than the one running the proof.
+  virtual bool isCancelled() { return false; }
   virtual KeyStore& getKeyStore() = 0;
 };
 
@@ -89,6 +130,18 @@ public:
   // Called after the system is halted, gets final memory state & final output.
   virtual void onHalt(const MemoryState& mem, const std::array<uint32_t, 8>& output) {}
 
+  // Called periodically, return true to abort the proof.
+  virtual bool isCancelled();
+
+  // How often, in cycles, to call onSample, or 0 to never call it.
+  virtual uint32_t getSampleInterval();
+
+  // Called every getSampleInterval() cycles with the guest call stack, innermost frame first.
+  virtual void onSample(const std::vector<uint32_t>& stack);
+
+  // Called whenever the guest uses more of its stack than before, with the bytes in use.
+  virtual void onStackUsage(uint32_t bytes);
+
 private:
   IoHandler* io;
 };
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 #include "risc0/core/log.h"
+#include "risc0/zkvm/platform/io.h"
 #include "risc0/zkvm/prove/exec.h"
 
 #include <gtest/gtest.h>
@@ -48,4 +49,14 @@ TEST(Step, HW) {
   ASSERT_THROW(run("test_invalid_addr"), std::runtime_error);
 }
 
+TEST(Step, SpanNameOutOfRange) {
+  // A span name longer than memory is rejected before anything is allocated for it.
+  MemoryState mem;
+  MemoryHandler io;
+  mem.store(0x1000, 0x2000);
+  mem.store(0x1004, 0xfffffff0);
+  mem.store(0x1008, 1);
+  ASSERT_THROW(io.onWrite(mem, 0, kGPIO_Span, 0x1000), std::out_of_range);
+}
+
 } // namespace risc0
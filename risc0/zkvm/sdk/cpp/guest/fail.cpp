@@ -19,7 +19,7 @@
 namespace risc0 {
 
 [[noreturn]] void fail(const char* msg) {
-  volatile FaultDescriptor desc{reinterpret_cast<uint32_t>(msg)};
+  volatile FaultDescriptor desc{reinterpret_cast<uint32_t>(msg), 0, 0, 0};
   // A compliant host should fault when it receives this descriptor.
   *GPIO_Fault() = &desc;
   // As a fallback for uncompliant hosts, force an unaligned write, which causes a fault within the
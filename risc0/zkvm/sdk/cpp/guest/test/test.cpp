@@ -19,6 +19,10 @@
 #include "risc0/zkvm/prove/riscv.h"
 #include "risc0/zkvm/sdk/cpp/host/receipt.h"
 
+#include <fstream>
+#include <iterator>
+#include <sstream>
+
 #include <gtest/gtest.h>
 
 using namespace risc0;
@@ -144,6 +148,278 @@ TEST_P(CoreTests, Fail) {
   EXPECT_THROW(prove(*circuit), std::runtime_error);
 }
 
+TEST_P(CoreTests, ElfBytes) {
+  std::string elfPath = GetParam().prefix + "test_sha";
+  std::ifstream is(elfPath, std::ios::binary);
+  BufferU8 elf((std::istreambuf_iterator<char>(is)), std::istreambuf_iterator<char>());
+
+  Prover prover(elf);
+  prover.writeInput(uint32_t(0));
+  Receipt receipt = prover.run();
+  receipt.verify(elf);
+  receipt.verify(elfPath);
+
+  // A truncated image is rejected rather than read out of bounds.
+  BufferU8 truncated(elf.begin(), elf.begin() + 16);
+  EXPECT_THROW(Prover(truncated).run(), std::runtime_error);
+}
+
+TEST(CoreTests, RustMain) {
+  std::string elfPath = "risc0/zkvm/sdk/rust/guest/test_main";
+
+  // The input is read as the argument and the result is committed.
+  Prover prover(elfPath);
+  prover.writeInput(uint32_t(5));
+  Receipt receipt = prover.run();
+  receipt.verify(elfPath);
+  ReceiptReader reader(receipt);
+  EXPECT_EQ(reader.read<uint32_t>(), 20u);
+
+  // An error result fails the guest, without a source location.
+  Prover failing(elfPath);
+  failing.writeInput(uint32_t(0));
+  try {
+    failing.run();
+    FAIL() << "Expected GuestFault";
+  } catch (const GuestFault& fault) {
+    EXPECT_EQ(fault.msg, "\"Division by zero\"");
+    EXPECT_EQ(fault.file, "");
+  }
+}
+
+TEST(CoreTests, RustPanicLocation) {
+  Prover prover("risc0/zkvm/sdk/rust/guest/test_fail");
+  try {
+    prover.run();
+    FAIL() << "Expected GuestFault";
+  } catch (const GuestFault& fault) {
+    EXPECT_EQ(fault.msg, "Failure");
+    EXPECT_EQ(fault.file, "risc0/zkvm/sdk/rust/guest/tests/fail.rs");
+    EXPECT_EQ(fault.line, 22u);
+    EXPECT_EQ(fault.column, 5u);
+    EXPECT_STREQ(fault.what(),
+                 "Guest panicked at risc0/zkvm/sdk/rust/guest/tests/fail.rs:22:5: Failure");
+  }
+}
+
+GuestFault runPanic(uint32_t mode, BufferU8* output = nullptr) {
+  Prover prover("risc0/zkvm/sdk/rust/guest/test_panic");
+  prover.writeInput(mode);
+  try {
+    prover.run();
+  } catch (const GuestFault& fault) {
+    if (output) {
+      *output = prover.getOutput();
+    }
+    return fault;
+  }
+  throw std::runtime_error("Expected GuestFault");
+}
+
+TEST(CoreTests, RustPanicOutOfMemory) {
+  GuestFault fault = runPanic(0);
+  EXPECT_EQ(fault.msg, "Out of memory: failed to allocate 2097152 bytes");
+}
+
+TEST(CoreTests, RustPanicTruncated) {
+  GuestFault fault = runPanic(1);
+  EXPECT_EQ(fault.msg, std::string(1023, 'x'));
+}
+
+TEST(CoreTests, RustPanicHook) {
+  BufferU8 output;
+  GuestFault fault = runPanic(2, &output);
+  EXPECT_EQ(fault.msg, "Hooked");
+  EXPECT_EQ(output, BufferU8({0xad, 0xde, 0, 0}));
+}
+
+TEST(CoreTests, RustCycleLimit) {
+  // Loading the image alone takes more cycles than this.
+  Prover prover("risc0/zkvm/sdk/rust/guest/test_main");
+  prover.setMaxCycles(64);
+  prover.writeInput(uint32_t(5));
+  EXPECT_THROW(prover.run(), CycleLimitExceeded);
+}
+
+TEST(CoreTests, RustCancelWhileProving) {
+  // Count the polls of a whole proof, then cancel on the last one, which comes from FRI.
+  size_t polls = 0;
+  {
+    Prover prover("risc0/zkvm/sdk/rust/guest/test_main");
+    prover.setCancelCallback([&]() {
+      polls++;
+      return false;
+    });
+    prover.writeInput(uint32_t(5));
+    prover.run();
+  }
+  Prover prover("risc0/zkvm/sdk/rust/guest/test_main");
+  prover.setCancelCallback([&]() { return --polls == 0; });
+  prover.writeInput(uint32_t(5));
+  EXPECT_THROW(prover.run(), Cancelled);
+}
+
+TEST(CoreTests, RustMemoryLayout) {
+  // The BUILD rule of test_layout moves the heap to 0x60000 and grows it to 1.25MB, and moves the
+  // input and commit regions.
+  std::string elfPath = "risc0/zkvm/sdk/rust/guest/test_layout";
+  Prover prover(elfPath);
+  prover.writeInput(uint32_t(1200 * 1024));
+  Receipt receipt = prover.run();
+  receipt.verify(elfPath);
+  ReceiptReader reader(receipt);
+  uint32_t addr = reader.read<uint32_t>();
+  EXPECT_GE(addr, 0x60000u);
+  EXPECT_LE(addr + 1200 * 1024, 0x1A0000u);
+
+  Prover failing(elfPath);
+  failing.writeInput(uint32_t(1300 * 1024));
+  EXPECT_THROW(failing.run(), GuestFault);
+}
+
+TEST(CoreTests, RustLongJournal) {
+  // The BUILD rule of test_journal shrinks the commit region to 4KB, and each commit is a word.
+  std::string elfPath = "risc0/zkvm/sdk/rust/guest/test_journal";
+  for (uint32_t count : {8u, 1000u, 3000u}) {
+    Prover prover(elfPath);
+    size_t parts = 0;
+    prover.setCommitCallback([&](const BufferU8&) { parts++; });
+    prover.writeInput(count);
+    Receipt receipt = prover.run();
+    receipt.verify(elfPath);
+    EXPECT_EQ(receipt.journal.size(), count * sizeof(uint32_t));
+    // The region goes to the host in one part, and so does each commit after it.
+    EXPECT_EQ(parts, count <= 1024 ? 1u : 1 + count - 1024);
+    ReceiptReader reader(receipt);
+    for (uint32_t i = 0; i < count; i++) {
+      ASSERT_EQ(reader.read<uint32_t>(), i);
+    }
+  }
+}
+
+TEST(CoreTests, RustTryRead) {
+  std::string elfPath = "risc0/zkvm/sdk/rust/guest/test_read";
+  Prover prover(elfPath);
+  uint8_t bytes[] = {0xff, 0xfe};
+  prover.writeInput(uint32_t(sizeof(bytes)));
+  prover.writeInput(bytes, sizeof(bytes));
+  Receipt receipt = prover.run();
+  receipt.verify(elfPath);
+  ReceiptReader reader(receipt);
+  EXPECT_EQ(reader.read<uint32_t>(), 1u);
+  EXPECT_EQ(reader.read<uint32_t>(), sizeof(bytes));
+}
+
+TEST(CoreTests, RustStackUsage) {
+  // The BUILD rule of test_stack shrinks the stack to 16KB, and each level of recursion takes a
+  // little over 1KB of it.
+  std::string elfPath = "risc0/zkvm/sdk/rust/guest/test_stack";
+  Prover prover(elfPath);
+  prover.writeInput(uint32_t(4));
+  prover.run();
+  uint32_t usage = prover.getStats().maxStackUsage;
+  EXPECT_GT(usage, 4u * 1024);
+  EXPECT_LT(usage, 16u * 1024);
+
+  Prover failing(elfPath);
+  failing.writeInput(uint32_t(32));
+  try {
+    failing.run();
+    FAIL() << "Expected GuestFault";
+  } catch (const GuestFault& fault) {
+    EXPECT_EQ(fault.msg, "Guest stack overflow");
+  }
+}
+
+TEST(CoreTests, RustWriteUnbounded) {
+  // 80 writes of 4KB each add up to more than the 256KB output region.
+  Prover prover("risc0/zkvm/sdk/rust/guest/test_write");
+  prover.writeInput(uint32_t(80));
+  prover.writeInput(uint32_t(1024));
+  size_t writes = 0;
+  prover.setWriteCallback([&](const BufferU8& buf) {
+    // Each write is the length of the record followed by its words.
+    ASSERT_EQ(buf.size(), size_t(1025 * 4));
+    writes++;
+  });
+  prover.run();
+  EXPECT_EQ(writes, size_t(80));
+  EXPECT_EQ(prover.getOutput().size(), size_t(80 * 1025 * 4));
+}
+
+std::vector<std::string> runLog(uint32_t level) {
+  std::vector<std::string> lines;
+  Prover prover("risc0/zkvm/sdk/rust/guest/test_log");
+  prover.setGuestLogLevel(level);
+  prover.setLogCallback([&](uint32_t cycle, const std::string& msg) { lines.push_back(msg); });
+  prover.run();
+  return lines;
+}
+
+TEST(CoreTests, RustPrint) {
+  std::vector<std::string> expected = {
+      "Hello, world!",
+      "",
+      std::string(255, 'y'),
+      std::string(45, 'y'),
+      "to stderr",
+      "Unterminated",
+  };
+  EXPECT_EQ(runLog(kGuestLogOff), expected);
+}
+
+TEST(CoreTests, RustLogLevel) {
+  std::vector<std::string> lines = runLog(kGuestLogInfo);
+  ASSERT_EQ(lines.size(), size_t(8));
+  EXPECT_EQ(lines[5], "ERROR test: failed 1");
+  EXPECT_EQ(lines[6], "INFO  test: info");
+
+  lines = runLog(kGuestLogTrace);
+  ASSERT_EQ(lines.size(), size_t(9));
+  EXPECT_EQ(lines[7], "DEBUG test: debug");
+}
+
+TEST(CoreTests, RustLogLevelSilentGuest) {
+  // Every Rust guest asks the host for its log level at startup, whether or
+  // not it logs anything.
+  std::string elfPath = "risc0/zkvm/sdk/rust/guest/test_main";
+  for (uint32_t level : {kGuestLogError, kGuestLogInfo, kGuestLogTrace}) {
+    Prover prover(elfPath);
+    prover.setGuestLogLevel(level);
+    prover.writeInput(uint32_t(5));
+    Receipt receipt = prover.run();
+    receipt.verify(elfPath);
+    ReceiptReader reader(receipt);
+    EXPECT_EQ(reader.read<uint32_t>(), 20u);
+  }
+}
+
+TEST(CoreTests, RustProfile) {
+  Prover prover("risc0/zkvm/sdk/rust/guest/test_profile");
+  prover.writeInput(uint32_t(1000));
+  prover.run();
+  const std::map<std::string, SpanStats>& spans = prover.getStats().spans;
+  ASSERT_EQ(spans.size(), size_t(2));
+  const SpanStats& mainSpan = spans.at("main");
+  const SpanStats& workSpan = spans.at("work");
+  EXPECT_EQ(mainSpan.count, uint64_t(1));
+  EXPECT_EQ(workSpan.count, uint64_t(2));
+  EXPECT_GT(workSpan.cycles, uint64_t(2000));
+  EXPECT_GT(mainSpan.cycles, workSpan.cycles);
+  EXPECT_GT(uint64_t(prover.readOutput<uint32_t>()), mainSpan.cycles);
+}
+
+TEST(CoreTests, RustSampleProfile) {
+  Prover prover("risc0/zkvm/sdk/rust/guest/test_profile");
+  prover.setSampleInterval(64);
+  prover.writeInput(uint32_t(1000));
+  prover.run();
+  std::stringstream ss;
+  prover.writeProfile(ss);
+  // Most samples land in the loop of `work`, below its caller.
+  EXPECT_NE(ss.str().find(";test_profile::work "), std::string::npos) << ss.str();
+}
+
 void doMemcpyTest(uint32_t srcOffset, uint32_t destOffset, uint32_t size) {
   // Make src + dest buffers of test patterns
   std::vector<uint8_t> srcBuf(1024);
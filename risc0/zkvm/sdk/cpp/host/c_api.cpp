@@ -14,10 +14,13 @@
 
 #include "risc0/zkvm/sdk/cpp/host/c_api.h"
 
+#include <cstring>
+#include <fstream>
 #include <memory>
 
 #include "risc0/core/log.h"
 #include "risc0/zkp/verify/verify.h"
+#include "risc0/zkvm/prove/exec.h"
 #include "risc0/zkvm/sdk/cpp/host/receipt.h"
 
 extern "C" {
@@ -28,10 +31,38 @@ struct risc0_string {
 
 } // extern "C"
 
+static void clear_error(risc0_error* err) {
+  err->msg = nullptr;
+  err->code = RISC0_ERROR_EXCEPTION;
+  err->fault_msg = nullptr;
+  err->fault_file = nullptr;
+  err->fault_line = 0;
+  err->fault_column = 0;
+}
+
+static void set_fault(risc0_error* err, const risc0::GuestFault& ex) {
+  err->msg = new risc0_string{ex.what()};
+  err->code = RISC0_ERROR_GUEST_FAULT;
+  err->fault_msg = new risc0_string{ex.msg};
+  if (!ex.file.empty()) {
+    err->fault_file = new risc0_string{ex.file};
+    err->fault_line = ex.line;
+    err->fault_column = ex.column;
+  }
+}
+
 template <typename F> void ffi_wrap_void(risc0_error* err, F fn) {
   try {
-    err->msg = nullptr;
+    clear_error(err);
     fn();
+  } catch (const risc0::CycleLimitExceeded& ex) {
+    err->msg = new risc0_string{ex.what()};
+    err->code = RISC0_ERROR_CYCLE_LIMIT;
+  } catch (const risc0::Cancelled& ex) {
+    err->msg = new risc0_string{ex.what()};
+    err->code = RISC0_ERROR_CANCELLED;
+  } catch (const risc0::GuestFault& ex) {
+    set_fault(err, ex);
   } catch (const std::exception& ex) {
     err->msg = new risc0_string{ex.what()};
   } catch (...) {
@@ -41,8 +72,19 @@ template <typename F> void ffi_wrap_void(risc0_error* err, F fn) {
 
 template <typename T, typename F> T ffi_wrap(risc0_error* err, T val, F fn) {
   try {
-    err->msg = nullptr;
+    clear_error(err);
     return fn();
+  } catch (const risc0::CycleLimitExceeded& ex) {
+    err->msg = new risc0_string{ex.what()};
+    err->code = RISC0_ERROR_CYCLE_LIMIT;
+    return val;
+  } catch (const risc0::Cancelled& ex) {
+    err->msg = new risc0_string{ex.what()};
+    err->code = RISC0_ERROR_CANCELLED;
+    return val;
+  } catch (const risc0::GuestFault& ex) {
+    set_fault(err, ex);
+    return val;
   } catch (const std::exception& ex) {
     err->msg = new risc0_string{ex.what()};
     return val;
@@ -68,6 +110,10 @@ void risc0_init() {
   }
 }
 
+void risc0_set_log_level(uint32_t level) {
+  risc0::setLogLevel(level);
+}
+
 const char* risc0_string_ptr(risc0_string* str) {
   return str->str.c_str();
 }
@@ -82,10 +128,91 @@ risc0_prover* risc0_prover_new(risc0_error* err, const char* elf_path) {
       err, nullptr, [&] { return new risc0_prover{std::make_unique<risc0::Prover>(elf_path)}; });
 }
 
+risc0_prover* risc0_prover_new_from_elf(risc0_error* err, const uint8_t* elf, size_t len) {
+  return ffi_wrap<risc0_prover*>(err, nullptr, [&] {
+    risc0::BufferU8 buf(elf, elf + len);
+    return new risc0_prover{std::make_unique<risc0::Prover>(buf)};
+  });
+}
+
 void risc0_prover_free(risc0_error* err, risc0_prover* ptr) {
   ffi_wrap_void(err, [&] { delete ptr; });
 }
 
+void risc0_prover_set_log_callback(risc0_error* err,
+                                   risc0_prover* ptr,
+                                   risc0_log_callback callback,
+                                   void* ctx) {
+  ffi_wrap_void(err, [&] {
+    if (callback) {
+      ptr->prover->setLogCallback([callback, ctx](uint32_t cycle, const std::string& msg) {
+        callback(ctx, cycle, msg.c_str());
+      });
+    } else {
+      ptr->prover->setLogCallback(nullptr);
+    }
+  });
+}
+
+void risc0_prover_set_io_callbacks(risc0_error* err,
+                                   risc0_prover* ptr,
+                                   risc0_io_callback on_write,
+                                   risc0_io_callback on_commit,
+                                   void* ctx) {
+  ffi_wrap_void(err, [&] {
+    auto wrap = [ctx](risc0_io_callback callback) -> risc0::Prover::IoCallback {
+      if (!callback) {
+        return nullptr;
+      }
+      return [callback, ctx](const risc0::BufferU8& buf) {
+        callback(ctx, buf.data(), buf.size());
+      };
+    };
+    ptr->prover->setWriteCallback(wrap(on_write));
+    ptr->prover->setCommitCallback(wrap(on_commit));
+  });
+}
+
+void risc0_prover_set_cancel_callback(risc0_error* err,
+                                      risc0_prover* ptr,
+                                      risc0_cancel_callback callback,
+                                      void* ctx) {
+  ffi_wrap_void(err, [&] {
+    if (callback) {
+      ptr->prover->setCancelCallback([callback, ctx]() { return callback(ctx) != 0; });
+    } else {
+      ptr->prover->setCancelCallback(nullptr);
+    }
+  });
+}
+
+void risc0_prover_set_guest_log_level(risc0_error* err, risc0_prover* ptr, uint32_t level) {
+  ffi_wrap_void(err, [&] { ptr->prover->setGuestLogLevel(level); });
+}
+
+void risc0_prover_set_max_cycles(risc0_error* err, risc0_prover* ptr, size_t max_cycles) {
+  ffi_wrap_void(err, [&] { ptr->prover->setMaxCycles(max_cycles); });
+}
+
+void risc0_prover_set_sample_interval(risc0_error* err, risc0_prover* ptr, uint32_t cycles) {
+  ffi_wrap_void(err, [&] { ptr->prover->setSampleInterval(cycles); });
+}
+
+void risc0_prover_set_key(risc0_error* err,
+                          risc0_prover* ptr,
+                          const char* name,
+                          const uint32_t* key,
+                          size_t len) {
+  ffi_wrap_void(err, [&] {
+    risc0::Key value;
+    if (len != sizeof(value.data) / sizeof(uint32_t)) {
+      throw std::runtime_error("Invalid key length");
+    }
+    std::memcpy(value.data, key, sizeof(value.data));
+    ptr->prover->setKey(name, value);
+  });
+}
+
 void risc0_prover_add_input(risc0_error* err, risc0_prover* ptr, const uint8_t* buf, size_t len) {
   ffi_wrap_void(err, [&] { ptr->prover->writeInput(buf, len); });
 }
@@ -105,10 +232,52 @@ risc0_receipt* risc0_prover_run(risc0_error* err, risc0_prover* ptr) {
   });
 }
 
+void risc0_prover_write_profile(risc0_error* err, risc0_prover* ptr, const char* path) {
+  ffi_wrap_void(err, [&] {
+    std::ofstream os(path);
+    if (!os) {
+      throw std::runtime_error(std::string("Could not open profile: ") + path);
+    }
+    ptr->prover->writeProfile(os);
+  });
+}
+
+size_t risc0_prover_get_span_count(risc0_error* err, const risc0_prover* ptr) {
+  return ffi_wrap(err, 0, [&] { return ptr->prover->getStats().spans.size(); });
+}
+
+const char* risc0_prover_get_span(risc0_error* err,
+                                  const risc0_prover* ptr,
+                                  size_t idx,
+                                  uint64_t* count,
+                                  uint64_t* cycles) {
+  return ffi_wrap<const char*>(err, nullptr, [&] {
+    const auto& spans = ptr->prover->getStats().spans;
+    if (idx >= spans.size()) {
+      throw std::out_of_range("Span index out of range");
+    }
+    auto it = std::next(spans.begin(), idx);
+    *count = it->second.count;
+    *cycles = it->second.cycles;
+    return it->first.c_str();
+  });
+}
+
+uint32_t risc0_prover_get_max_stack_usage(risc0_error* err, const risc0_prover* ptr) {
+  return ffi_wrap(err, 0, [&] { return ptr->prover->getStats().maxStackUsage; });
+}
+
 void risc0_receipt_verify(risc0_error* err, const char* elf_path, const risc0_receipt* ptr) {
   ffi_wrap_void(err, [&] { ptr->receipt.verify(elf_path); });
 }
 
+void risc0_receipt_verify_elf(risc0_error* err,
+                              const uint8_t* elf,
+                              size_t len,
+                              const risc0_receipt* ptr) {
+  ffi_wrap_void(err, [&] { ptr->receipt.verify(risc0::BufferU8(elf, elf + len)); });
+}
+
 const uint32_t* risc0_receipt_get_seal_buf(risc0_error* err, const risc0_receipt* ptr) {
   return ffi_wrap<const uint32_t*>(err, nullptr, [&] { return ptr->receipt.seal.data(); });
 }
@@ -38,10 +38,31 @@ typedef struct risc0_receipt risc0_receipt;
 // Error
 //
 
+enum {
+  RISC0_ERROR_EXCEPTION = 0,
+  RISC0_ERROR_CYCLE_LIMIT = 1,
+  RISC0_ERROR_CANCELLED = 2,
+  RISC0_ERROR_GUEST_FAULT = 3,
+};
+
 typedef struct {
   risc0_string* msg;
+  uint32_t code;
+  // For RISC0_ERROR_GUEST_FAULT, the message reported by the guest and, if it reported one, the
+  // source location of the failure. Otherwise null.
+  risc0_string* fault_msg;
+  risc0_string* fault_file;
+  uint32_t fault_line;
+  uint32_t fault_column;
 } risc0_error;
 
+typedef void (*risc0_log_callback)(void* ctx, uint32_t cycle, const char* msg);
+
+typedef void (*risc0_io_callback)(void* ctx, const uint8_t* buf, size_t len);
+
+// Returns non-zero to cancel. May be called from any thread.
+typedef uint32_t (*risc0_cancel_callback)(void* ctx);
+
 //
 // String
 //
@@ -55,14 +76,46 @@ void risc0_string_free(risc0_string* str);
 //
 void risc0_init();
 
+void risc0_set_log_level(uint32_t level);
+
 //
 // Prover
 //
 
 risc0_prover* risc0_prover_new(risc0_error* err, const char* elf_path);
 
+risc0_prover* risc0_prover_new_from_elf(risc0_error* err, const uint8_t* elf, size_t len);
+
 void risc0_prover_free(risc0_error* err, risc0_prover* ptr);
 
+void risc0_prover_set_log_callback(risc0_error* err,
+                                   risc0_prover* ptr,
+                                   risc0_log_callback callback,
+                                   void* ctx);
+
+void risc0_prover_set_io_callbacks(risc0_error* err,
+                                   risc0_prover* ptr,
+                                   risc0_io_callback on_write,
+                                   risc0_io_callback on_commit,
+                                   void* ctx);
+
+void risc0_prover_set_cancel_callback(risc0_error* err,
+                                      risc0_prover* ptr,
+                                      risc0_cancel_callback callback,
+                                      void* ctx);
+
+void risc0_prover_set_guest_log_level(risc0_error* err, risc0_prover* ptr, uint32_t level);
+
+void risc0_prover_set_max_cycles(risc0_error* err, risc0_prover* ptr, size_t max_cycles);
+
+void risc0_prover_set_sample_interval(risc0_error* err, risc0_prover* ptr, uint32_t cycles);
+
+void risc0_prover_set_key(risc0_error* err,
+                          risc0_prover* ptr,
+                          const char* name,
+                          const uint32_t* key,
+                          size_t len);
+
 void risc0_prover_add_input(risc0_error* err, risc0_prover* ptr, const uint8_t* buf, size_t len);
 
 size_t risc0_prover_get_num_outputs(risc0_error* err, risc0_prover* ptr);
@@ -71,12 +124,31 @@ const void* risc0_prover_get_output(risc0_error* err, risc0_prover* ptr, size_t
 
 risc0_receipt* risc0_prover_run(risc0_error* err, risc0_prover* ptr);
 
+// Writes the sampled call stacks to 'path' as folded stacks.
+void risc0_prover_write_profile(risc0_error* err, risc0_prover* ptr, const char* path);
+
+size_t risc0_prover_get_span_count(risc0_error* err, const risc0_prover* ptr);
+
+// Returns the name of the span at 'idx', in name order, which is valid until the prover is freed.
+const char* risc0_prover_get_span(risc0_error* err,
+                                  const risc0_prover* ptr,
+                                  size_t idx,
+                                  uint64_t* count,
+                                  uint64_t* cycles);
+
+uint32_t risc0_prover_get_max_stack_usage(risc0_error* err, const risc0_prover* ptr);
+
 //
 // Proof
 //
 
 void risc0_receipt_verify(risc0_error* err, const char* method_id_path, const risc0_receipt* ptr);
 
+void risc0_receipt_verify_elf(risc0_error* err,
+                              const uint8_t* elf,
+                              size_t len,
+                              const risc0_receipt* ptr);
+
 const uint32_t* risc0_receipt_get_seal_buf(risc0_error* err, const risc0_receipt* ptr);
 
 size_t risc0_receipt_get_seal_len(risc0_error* err, const risc0_receipt* ptr);
@@ -0,0 +1,88 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "risc0/zkvm/sdk/cpp/host/profiler.h"
+
+#include <cstdlib>
+#include <cxxabi.h>
+#include <iomanip>
+#include <memory>
+#include <sstream>
+
+namespace risc0 {
+
+namespace {
+
+// Demangles Rust (legacy mangling) and C++ symbol names, dropping the hash suffix of Rust names.
+std::string demangle(const std::string& name) {
+  int status = 0;
+  std::unique_ptr<char, decltype(&std::free)> demangled(
+      abi::__cxa_demangle(name.c_str(), nullptr, nullptr, &status), std::free);
+  if (status != 0) {
+    return name;
+  }
+  std::string out(demangled.get());
+  size_t hash = out.rfind("::h");
+  if (hash != std::string::npos && out.size() - hash == 3 + 16) {
+    out.resize(hash);
+  }
+  return out;
+}
+
+} // namespace
+
+Profiler::Profiler(const std::vector<uint8_t>& elf) {
+  for (const ElfSymbol& symbol : loadElfSymbols(elf)) {
+    symbols.emplace(symbol.addr, symbol);
+  }
+}
+
+void Profiler::addSample(const std::vector<uint32_t>& stack) {
+  samples[stack]++;
+}
+
+std::string Profiler::symbolize(uint32_t addr) const {
+  auto it = symbols.upper_bound(addr);
+  if (it != symbols.begin()) {
+    const ElfSymbol& symbol = std::prev(it)->second;
+    if (addr < symbol.addr + symbol.size) {
+      return demangle(symbol.name);
+    }
+  }
+  std::stringstream ss;
+  ss << "0x" << std::hex << std::setw(8) << std::setfill('0') << addr;
+  return ss.str();
+}
+
+void Profiler::writeFolded(std::ostream& os) const {
+  // Different addresses within the same functions fold into the same line.
+  std::map<std::string, uint64_t> folded;
+  for (const auto& [stack, count] : samples) {
+    std::string line;
+    for (size_t i = stack.size(); i-- > 0;) {
+      // Return addresses point after the call, which may be past the end of the caller.
+      uint32_t addr = i == 0 ? stack[i] : stack[i] - 4;
+      if (!line.empty()) {
+        line += ';';
+      }
+      line += symbolize(addr);
+    }
+    folded[line] += count;
+  }
+  for (const auto& [line, count] : folded) {
+    os << line << ' ' << count << '\n';
+  }
+}
+
+} // namespace risc0
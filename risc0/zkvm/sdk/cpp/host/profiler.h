@@ -0,0 +1,49 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include "risc0/core/elf.h"
+
+#include <cstdint>
+#include <map>
+#include <ostream>
+#include <string>
+#include <vector>
+
+namespace risc0 {
+
+// Collects sampled guest call stacks and writes them as folded stacks, the input format of flame
+// graph tools such as flamegraph.pl and inferno.
+class Profiler {
+public:
+  // Symbolizes addresses with the function symbols of the guest's ELF image.
+  Profiler(const std::vector<uint8_t>& elf);
+
+  // Adds a call stack, innermost frame first, as returned by ExecState::getCallStack.
+  void addSample(const std::vector<uint32_t>& stack);
+
+  // Writes one line for each distinct stack: the function names outermost first, separated by
+  // semicolons, followed by the number of samples.
+  void writeFolded(std::ostream& os) const;
+
+private:
+  std::string symbolize(uint32_t addr) const;
+
+  // Function symbols by address.
+  std::map<uint32_t, ElfSymbol> symbols;
+  std::map<std::vector<uint32_t>, uint64_t> samples;
+};
+
+} // namespace risc0
@@ -14,12 +14,15 @@
 
 #include "risc0/zkvm/sdk/cpp/host/receipt.h"
 
+#include "risc0/core/elf.h"
 #include "risc0/core/log.h"
 #include "risc0/zkp/core/sha256_cpu.h"
 #include "risc0/zkp/prove/prove.h"
 #include "risc0/zkp/verify/verify.h"
+#include "risc0/zkvm/prove/layout.h"
 #include "risc0/zkvm/prove/method_id.h"
 #include "risc0/zkvm/prove/riscv.h"
+#include "risc0/zkvm/sdk/cpp/host/profiler.h"
 #include "risc0/zkvm/verify/riscv.h"
 
 #include <sstream>
@@ -28,7 +31,14 @@ namespace risc0 {
 
 void Receipt::verify(const std::string& filename) const {
   LOG(1, "Reading code id from " << filename + ".id");
-  MethodID code = readMethodID(filename + ".id");
+  verify(readMethodID(filename + ".id"));
+}
+
+void Receipt::verify(const BufferU8& elf) const {
+  verify(makeMethodID(elf));
+}
+
+void Receipt::verify(const MethodID& code) const {
   std::unique_ptr<VerifyCircuit> circuit = getRiscVVerifyCircuit(code);
   risc0::verify(*circuit, seal.data(), seal.size());
   if (journal.size() != seal[8]) {
@@ -50,8 +60,9 @@ void Receipt::verify(const std::string& filename) const {
 }
 
 struct Prover::Impl : public IoHandler {
-  Impl(const std::string& elfPath)
+  Impl(const std::string& elfPath, const BufferU8& elf)
       : elfPath(elfPath)
+      , elf(elf)
       , outputStream(outputBuffer)
       , commitStream(commitBuffer)
       , inputWriter(inputStream)
@@ -62,9 +73,9 @@ struct Prover::Impl : public IoHandler {
 
   void onInit(MemoryState& mem) override {
     LOG(1, "Prover::onInit>");
-    uint32_t addr = kMemInputStart;
+    uint32_t addr = layout.input.start;
     for (uint32_t word : inputStream.vec) {
-      if (addr > kMemInputEnd) {
+      if (addr >= layout.input.end) {
         throw std::runtime_error("Out of memory: inputs");
       }
       LOG(1, "  " << hex(addr) << ": " << hex(word));
@@ -76,17 +87,71 @@ struct Prover::Impl : public IoHandler {
   void onWrite(const BufferU8& buf) override {
     LOG(1, "IoHandler::onWrite> " << buf.size());
     outputBuffer.insert(outputBuffer.end(), buf.begin(), buf.end());
+    if (writeCallback) {
+      writeCallback(buf);
+    }
   }
 
   void onCommit(const BufferU8& buf) override {
     LOG(1, "IoHandler::onCommit> " << buf.size());
     commitBuffer.insert(commitBuffer.end(), buf.begin(), buf.end());
+    if (commitCallback) {
+      commitCallback(buf);
+    }
+  }
+
+  void onLog(uint32_t cycle, const std::string& msg) override {
+    if (logCallback) {
+      logCallback(cycle, msg);
+    } else {
+      IoHandler::onLog(cycle, msg);
+    }
+  }
+
+  void onSpan(uint32_t cycle, const std::string& name, bool enter) override {
+    if (enter) {
+      openSpans.emplace_back(name, cycle);
+      return;
+    }
+    if (openSpans.empty() || openSpans.back().first != name) {
+      throw std::runtime_error("Guest exited span '" + name + "' out of order");
+    }
+    SpanStats& span = stats.spans[name];
+    span.count++;
+    span.cycles += cycle - openSpans.back().second;
+    openSpans.pop_back();
   }
 
+  uint32_t getSampleInterval() override { return sampleInterval; }
+
+  void onSample(const std::vector<uint32_t>& stack) override { profiler->addSample(stack); }
+
+  void onStackUsage(uint32_t bytes) override { stats.maxStackUsage = bytes; }
+
+  bool isCancelled() override { return cancelCallback && cancelCallback(); }
+
   KeyStore& getKeyStore() override { return keyStore; }
 
+  uint32_t getGuestLogLevel() override { return guestLogLevel; }
+
+  // Exactly one of elfPath and elf is set, depending on how the guest was loaded.
   std::string elfPath;
+  BufferU8 elf;
+  size_t maxCycles = kMaxCycles;
+  uint32_t guestLogLevel = kGuestLogInfo;
+  uint32_t sampleInterval = 0;
+  // Read from the ELF by run().
+  MemoryLayout layout;
+  // Created by run() when sampling.
+  std::unique_ptr<Profiler> profiler;
+  LogCallback logCallback;
+  IoCallback writeCallback;
+  IoCallback commitCallback;
+  CancelCallback cancelCallback;
   KeyStore keyStore;
+  RunStats stats;
+  // The spans the guest is inside of, innermost last, with the cycles they were entered on.
+  std::vector<std::pair<std::string, uint32_t>> openSpans;
   BufferU8 outputBuffer;
   BufferU8 commitBuffer;
   VectorStreamWriter inputStream;
@@ -125,7 +190,9 @@ void CheckedStreamReader::read_buffer(void* buf, size_t len) {
   cursor = end_cursor;
 }
 
-Prover::Prover(const std::string& elfPath) : impl(new Impl(elfPath)) {}
+Prover::Prover(const std::string& elfPath) : impl(new Impl(elfPath, {})) {}
+
+Prover::Prover(const BufferU8& elf) : impl(new Impl("", elf)) {}
 
 Prover::~Prover() = default;
 
@@ -137,6 +204,30 @@ void Prover::setKey(const std::string& name, const Key& key) {
   impl->getKeyStore()[name] = key;
 }
 
+void Prover::setLogCallback(LogCallback callback) {
+  impl->logCallback = std::move(callback);
+}
+
+void Prover::setCancelCallback(CancelCallback callback) {
+  impl->cancelCallback = std::move(callback);
+}
+
+void Prover::setWriteCallback(IoCallback callback) {
+  impl->writeCallback = std::move(callback);
+}
+
+void Prover::setCommitCallback(IoCallback callback) {
+  impl->commitCallback = std::move(callback);
+}
+
+void Prover::setGuestLogLevel(uint32_t level) {
+  impl->guestLogLevel = level;
+}
+
+void Prover::setMaxCycles(size_t maxCycles) {
+  impl->maxCycles = maxCycles;
+}
+
 const BufferU8& Prover::getOutput() {
   return impl->outputBuffer;
 }
@@ -182,16 +273,43 @@ void Prover::writeInput(const void* ptr, size_t size) {
   }
 }
 
+void Prover::setSampleInterval(uint32_t cycles) {
+  impl->sampleInterval = cycles;
+}
+
+const RunStats& Prover::getStats() {
+  return impl->stats;
+}
+
+void Prover::writeProfile(std::ostream& os) {
+  if (!impl->profiler) {
+    throw std::runtime_error("No profile was sampled, see setSampleInterval");
+  }
+  impl->profiler->writeFolded(os);
+}
+
 Receipt Prover::run() {
+  BufferU8 elf = impl->elfPath.empty() ? impl->elf : readElf(impl->elfPath);
+  impl->layout = loadMemoryLayout(elf);
+  // Statistics and samples describe the last run only.
+  impl->stats = RunStats();
+  impl->openSpans.clear();
+  impl->profiler = impl->sampleInterval ? std::make_unique<Profiler>(elf) : nullptr;
   // Set the memory handlers to call back to the impl
   MemoryHandler handler(impl.get());
   // Make the circuit
-  std::unique_ptr<ProveCircuit> circuit = getRiscVProveCircuit(impl->elfPath.c_str(), handler);
+  std::unique_ptr<ProveCircuit> circuit =
+      impl->elfPath.empty() ? getRiscVProveCircuit(impl->elf, handler, impl->maxCycles)
+                            : getRiscVProveCircuit(impl->elfPath, handler, impl->maxCycles);
   BufferU32 seal = prove(*circuit);
   // Attach the full version of the output journal + construct receipt object
   Receipt receipt{getCommit(), seal};
   // Verify receipt to make sure it works
-  receipt.verify(impl->elfPath);
+  if (impl->elfPath.empty()) {
+    receipt.verify(impl->elf);
+  } else {
+    receipt.verify(impl->elfPath);
+  }
   return receipt;
 }
 
@@ -20,7 +20,10 @@
 #include "risc0/zkvm/prove/step.h"
 #include "risc0/zkvm/verify/method_id.h"
 
+#include <functional>
+#include <map>
 #include <memory>
+#include <ostream>
 #include <vector>
 
 namespace risc0 {
@@ -43,6 +46,7 @@ private:
 static_assert(is_stream_reader<CheckedStreamReader>(),
               "CheckedStreamReader must conform to the stream reader model");
 
+// A Receipt is immutable once constructed, so it may be shared between threads.
 struct Receipt {
   BufferU8 journal;
   BufferU32 seal;
@@ -50,6 +54,12 @@ struct Receipt {
   // Verify a receipt against some code, throws if invalid.
   void verify(const std::string& elfPath) const;
 
+  // As above, but computes the method ID from an ELF image that is already in memory.
+  void verify(const BufferU8& elf) const;
+
+  // Verify a receipt against a known method ID, throws if invalid.
+  void verify(const MethodID& methodID) const;
+
   template <typename Archive> void transfer(Archive& ar) {
     ar.transfer(journal);
     ar.transfer(seal);
@@ -71,9 +81,37 @@ private:
   ArchiveReader<CheckedStreamReader> archive;
 };
 
+// Cycle counts of a guest profiling span, summed over every time the guest entered it.
+struct SpanStats {
+  uint64_t count = 0;
+  // Includes the cycles of nested spans, and counts recursive entries each time.
+  uint64_t cycles = 0;
+};
+
+// Statistics collected while running a guest.
+struct RunStats {
+  std::map<std::string, SpanStats> spans;
+  // The most bytes of stack the guest had in use at once.
+  uint32_t maxStackUsage = 0;
+};
+
+// A Prover may be moved to another thread, but must only be used from one thread at a time.
+// The callbacks are invoked on the thread calling run(), except for the cancel callback, which
+// must itself be thread safe.
 class Prover {
 public:
+  // Receives each log line emitted by the guest along with the cycle it was emitted on.
+  using LogCallback = std::function<void(uint32_t cycle, const std::string& msg)>;
+
+  // Receives data sent by the guest to the host as it is produced.
+  using IoCallback = std::function<void(const BufferU8& buf)>;
+
+  // Polled during run(); returning true makes run() throw Cancelled.
+  using CancelCallback = std::function<bool()>;
+
   Prover(const std::string& elfPath);
+  // Loads the guest from an ELF image that is already in memory.
+  Prover(const BufferU8& elf);
   ~Prover();
 
   // Allows access to key store to get/set keys
@@ -81,6 +119,30 @@ public:
 
   void setKey(const std::string& name, const Key& key);
 
+  // Redirect guest logs to the given callback instead of the default LOG output.
+  void setLogCallback(LogCallback callback);
+
+  // Observe guest writes and commits, in addition to collecting them in the output and journal.
+  // The write callback sees each env::write and env::commit as it happens. The commit callback
+  // sees the journal in order: once, when the guest exits, if it fits in the commit region, and
+  // otherwise the region once it fills up, followed by each later env::commit.
+  void setWriteCallback(IoCallback callback);
+  void setCommitCallback(IoCallback callback);
+
+  void setCancelCallback(CancelCallback callback);
+
+  // Limit the level of log records emitted by the guest's `log` crate facade. Defaults to
+  // kGuestLogInfo.
+  void setGuestLogLevel(uint32_t level);
+
+  // Limit the number of cycles the guest may run for. Defaults to kMaxCycles.
+  void setMaxCycles(size_t maxCycles);
+
+  // Sample the guest call stack every 'cycles' cycles while running, for writeProfile. Stacks are
+  // only complete if the guest was built with frame pointers. Defaults to 0, which disables
+  // sampling.
+  void setSampleInterval(uint32_t cycles);
+
   void writeInput(const void* ptr, size_t size);
 
   template <typename T> void writeInput(const T& obj) { getInputWriter().transfer(obj); }
@@ -103,6 +165,12 @@ public:
 
   Receipt run();
 
+  // Valid once run() has executed the guest, even if it failed.
+  const RunStats& getStats();
+
+  // Writes the call stacks sampled by run() as folded stacks, see setSampleInterval.
+  void writeProfile(std::ostream& os);
+
 private:
   ArchiveWriter<VectorStreamWriter>& getInputWriter();
   ArchiveReader<CheckedStreamReader>& getOutputReader();
@@ -0,0 +1,283 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Builds guest methods with Cargo, for use from a `build.rs` script.
+//!
+//! This is the Cargo counterpart of the Bazel `risc0_rust_method` rule: each
+//! listed guest crate is cross-compiled for `riscv32im` against the `risc0.ld`
+//! linker script, and each of its binaries gets a method ID from the `make-id`
+//! tool. A crate that embeds methods has a build script like:
+//!
+//! ```ignore
+//! fn main() {
+//!     zkvm_build::embed_methods(&["guest"]);
+//! }
+//! ```
+//!
+//! and includes the generated module with:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/methods.rs"));
+//! ```
+//!
+//! For a guest binary named `multiply`, the module then defines:
+//!
+//! * `MULTIPLY_PATH`: the path to the ELF, with the method ID alongside it at
+//!   `MULTIPLY_PATH.id` as `Prover::new` and `Receipt::verify` expect.
+//! * `MULTIPLY_ELF`: the bytes of the ELF.
+//! * `MULTIPLY_ID`: the bytes of the method ID.
+//!
+//! A guest can change the sizes of the stack, heap, input, output and commit
+//! regions from their defaults in `risc0.ld` in its `Cargo.toml`, in bytes:
+//!
+//! ```toml
+//! [package.metadata.risc0.memory]
+//! heap = 0x140000
+//! input = 0x10000
+//! ```
+//!
+//! Guests need a nightly toolchain with the `rust-src` component, since `core`
+//! and `alloc` are rebuilt for the target. The `make-id` tool is looked up on
+//! the `PATH` unless `RISC0_MAKE_ID` names it; `bazel build
+//! //risc0/zkvm/prove/make-id` builds it.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use serde_json::Value;
+
+const TARGET_NAME: &str = "riscv32im-unknown-none-elf";
+const TARGET_JSON: &str =
+    include_str!("../../../../../../bazel/rules/rust/riscv32im-unknown-none-elf.json");
+const LINKER_SCRIPT: &str = include_str!("../../../../platform/risc0.ld");
+/// The regions a guest can resize, see `risc0.ld`.
+const MEMORY_REGIONS: &[&str] = &["stack", "heap", "input", "output", "commit"];
+
+/// Builds the guest crates at `guests`, given relative to the directory of the
+/// calling package, and writes `methods.rs` into `OUT_DIR`.
+///
+/// Panics if any guest fails to build or to produce a method ID, which fails
+/// the build script.
+pub fn embed_methods(guests: &[&str]) {
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR is not set"));
+    let manifest_dir =
+        PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set"));
+
+    let target_json = out_dir.join(format!("{}.json", TARGET_NAME));
+    fs::write(&target_json, TARGET_JSON).expect("Failed to write target spec");
+
+    println!("cargo:rerun-if-env-changed=RISC0_MAKE_ID");
+    let mut methods = String::new();
+    for guest in guests {
+        let guest_dir = manifest_dir.join(guest);
+        println!("cargo:rerun-if-changed={}", guest_dir.display());
+        let (name, memory) = guest_metadata(&guest_dir);
+        let linker_script = out_dir.join(format!("{}.ld", name));
+        fs::write(&linker_script, linker_script_for(&memory))
+            .expect("Failed to write linker script");
+        let elfs = build_guest(
+            &guest_dir,
+            &target_json,
+            &linker_script,
+            &out_dir.join("guest"),
+        );
+        for elf in elfs {
+            let id = make_id(&elf);
+            methods.push_str(&method_consts(&elf, &id));
+        }
+    }
+    fs::write(out_dir.join("methods.rs"), methods).expect("Failed to write methods.rs");
+}
+
+/// Returns the package name of the guest crate in `guest_dir` and its
+/// `[package.metadata.risc0.memory]` table, which is null if it has none.
+fn guest_metadata(guest_dir: &Path) -> (String, Value) {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .args(["metadata", "--no-deps", "--format-version=1"])
+        .arg("--manifest-path")
+        .arg(guest_dir.join("Cargo.toml"))
+        .stderr(Stdio::inherit())
+        .output()
+        .expect("Failed to run cargo");
+    if !output.status.success() {
+        panic!("Failed to read guest metadata: {}", guest_dir.display());
+    }
+    let metadata: Value = serde_json::from_slice(&output.stdout).expect("Invalid cargo metadata");
+    let package = &metadata["packages"][0];
+    let name = package["name"].as_str().expect("Guest has no package name");
+    (
+        name.to_string(),
+        package["metadata"]["risc0"]["memory"].clone(),
+    )
+}
+
+/// Returns `risc0.ld`, preceded by the region sizes in `memory`, which it
+/// reads.
+fn linker_script_for(memory: &Value) -> String {
+    let mut script = String::new();
+    if let Some(memory) = memory.as_object() {
+        for (region, size) in memory {
+            if !MEMORY_REGIONS.contains(&region.as_str()) {
+                panic!(
+                    "Unknown memory region {}, expected one of {:?}",
+                    region, MEMORY_REGIONS
+                );
+            }
+            let size = size
+                .as_u64()
+                .unwrap_or_else(|| panic!("The size of the {} region is not an integer", region));
+            script.push_str(&format!("__{}_size = {:#x};\n", region, size));
+        }
+    }
+    script.push_str(LINKER_SCRIPT);
+    script
+}
+
+/// Cross-compiles the guest crate in `guest_dir` and returns the paths of the
+/// ELFs it produced.
+fn build_guest(
+    guest_dir: &Path,
+    target_json: &Path,
+    linker_script: &Path,
+    target_dir: &Path,
+) -> Vec<PathBuf> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    // Flags meant for the host build do not apply to the guest.
+    let rustflags = [
+        "-C".to_string(),
+        format!("link-arg=-T{}", linker_script.display()),
+        "-C".to_string(),
+        "overflow-checks=on".to_string(),
+    ]
+    .join("\x1f");
+    let output = Command::new(cargo)
+        .args(["build", "--release", "--message-format=json"])
+        .args(["-Z", "build-std=core,alloc"])
+        .args(["-Z", "build-std-features=compiler-builtins-mem"])
+        .arg("--manifest-path")
+        .arg(guest_dir.join("Cargo.toml"))
+        .arg("--target")
+        .arg(target_json)
+        .arg("--target-dir")
+        .arg(target_dir)
+        .env("CARGO_ENCODED_RUSTFLAGS", rustflags)
+        .env_remove("RUSTFLAGS")
+        .stderr(Stdio::inherit())
+        .output()
+        .expect("Failed to run cargo");
+    if !output.status.success() {
+        panic!("Failed to build guest: {}", guest_dir.display());
+    }
+    let stdout = String::from_utf8(output.stdout).expect("Invalid cargo output");
+    executables(&stdout)
+}
+
+/// Picks the executables out of cargo's `--message-format=json` output.
+fn executables(messages: &str) -> Vec<PathBuf> {
+    messages
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|msg| msg["reason"] == "compiler-artifact")
+        .filter_map(|msg| msg["executable"].as_str().map(PathBuf::from))
+        .collect()
+}
+
+/// Writes the method ID of `elf` to `<elf>.id`, as `risc0_method_id` does.
+fn make_id(elf: &Path) -> PathBuf {
+    let tool = env::var_os("RISC0_MAKE_ID").unwrap_or_else(|| "make-id".into());
+    let mut id = elf.as_os_str().to_owned();
+    id.push(".id");
+    let id = PathBuf::from(id);
+    let status = Command::new(&tool)
+        .arg(elf)
+        .arg(&id)
+        .status()
+        .unwrap_or_else(|err| panic!("Failed to run {:?}: {}", tool, err));
+    if !status.success() {
+        panic!("Failed to make method ID: {}", elf.display());
+    }
+    id
+}
+
+/// The generated constants for one method.
+fn method_consts(elf: &Path, id: &Path) -> String {
+    let name = elf
+        .file_name()
+        .and_then(|name| name.to_str())
+        .expect("Invalid guest name")
+        .to_uppercase()
+        .replace('-', "_");
+    let elf = elf.to_str().expect("Non UTF-8 guest path");
+    let id = id.to_str().expect("Non UTF-8 guest path");
+    format!(
+        "pub const {0}_PATH: &str = {1:?};\n\
+         pub const {0}_ELF: &[u8] = include_bytes!({1:?});\n\
+         pub const {0}_ID: &[u8] = include_bytes!({2:?});\n",
+        name, elf, id
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::{Path, PathBuf};
+
+    use serde_json::json;
+
+    use super::{executables, linker_script_for, method_consts, LINKER_SCRIPT};
+
+    #[test]
+    fn finds_executables() {
+        let messages = concat!(
+            r#"{"reason":"compiler-artifact","executable":null}"#,
+            "\n",
+            r#"{"reason":"compiler-artifact","executable":"/out/test-sha"}"#,
+            "\n",
+            r#"{"reason":"build-finished","success":true}"#,
+        );
+        assert_eq!(executables(messages), [PathBuf::from("/out/test-sha")]);
+    }
+
+    #[test]
+    fn linker_script() {
+        assert_eq!(linker_script_for(&json!(null)), LINKER_SCRIPT);
+        assert_eq!(
+            linker_script_for(&json!({"heap": 0x140000, "input": 0x10000})),
+            format!(
+                "__heap_size = 0x140000;\n__input_size = 0x10000;\n{}",
+                LINKER_SCRIPT
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown memory region data")]
+    fn linker_script_unknown_region() {
+        linker_script_for(&json!({"data": 0x1000}));
+    }
+
+    #[test]
+    fn consts() {
+        let consts = method_consts(Path::new("/out/test-sha"), Path::new("/out/test-sha.id"));
+        assert_eq!(
+            consts,
+            "pub const TEST_SHA_PATH: &str = \"/out/test-sha\";\n\
+             pub const TEST_SHA_ELF: &[u8] = include_bytes!(\"/out/test-sha\");\n\
+             pub const TEST_SHA_ID: &[u8] = include_bytes!(\"/out/test-sha.id\");\n"
+        );
+    }
+}
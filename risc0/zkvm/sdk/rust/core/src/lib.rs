@@ -4,6 +4,8 @@ use core::fmt::{Debug, Display, Formatter};
 
 use serde::{Deserialize, Serialize};
 
+pub mod sha;
+
 pub const DIGEST_WORDS: usize = 8;
 
 #[derive(Clone, Copy, Default, Deserialize, Eq, Hash, Ord, PartialOrd, PartialEq, Serialize)]
@@ -38,26 +40,186 @@ impl Debug for Digest {
     }
 }
 
-pub trait Log {
-    fn log(&self, msg: &str);
+/// The importance of a log message, in the same order as `log::Level`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        f.pad(match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Level> for log::Level {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Error => log::Level::Error,
+            Level::Warn => log::Level::Warn,
+            Level::Info => log::Level::Info,
+            Level::Debug => log::Level::Debug,
+            Level::Trace => log::Level::Trace,
+        }
+    }
+}
+
+/// Receives the messages logged with [log].
+pub trait Log: Sync {
+    /// Logs `msg`. `target` names the part of the program that logged it,
+    /// usually a module path.
+    fn log(&self, level: Level, target: &str, msg: &str);
+}
+
+/// Returned by [set_logger] when a logger has already been set.
+#[derive(Debug)]
+pub struct SetLoggerError(());
+
+impl Display for SetLoggerError {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        f.write_str("a logger has already been set")
+    }
 }
 
-static mut LOGGER: &dyn Log = &NopLogger;
+#[cfg(feature = "std")]
+impl std::error::Error for SetLoggerError {}
 
-struct NopLogger;
+/// Forwards messages to the `log` crate, so that they reach whichever logger
+/// the host program installed.
+#[cfg(feature = "std")]
+struct DefaultLogger;
 
-impl Log for NopLogger {
-    fn log(&self, _msg: &str) {}
+#[cfg(feature = "std")]
+impl Log for DefaultLogger {
+    fn log(&self, level: Level, target: &str, msg: &str) {
+        log::log!(target: target, level.into(), "{}", msg);
+    }
 }
 
-pub fn set_logger(logger: &'static dyn Log) {
-    unsafe { LOGGER = logger };
+/// Discards all messages.
+#[cfg(not(feature = "std"))]
+struct DefaultLogger;
+
+#[cfg(not(feature = "std"))]
+impl Log for DefaultLogger {
+    fn log(&self, _level: Level, _target: &str, _msg: &str) {}
+}
+
+#[cfg(feature = "std")]
+mod registry {
+    use std::sync::Once;
+
+    use super::{DefaultLogger, Log};
+
+    static INIT: Once = Once::new();
+    // Written once, by the closure passed to INIT, and only read after INIT
+    // has completed.
+    static mut LOGGER: &dyn Log = &DefaultLogger;
+
+    pub(super) fn set(logger: &'static dyn Log) -> bool {
+        let mut set = false;
+        INIT.call_once(|| {
+            unsafe { LOGGER = logger };
+            set = true;
+        });
+        set
+    }
+
+    pub(super) fn get() -> &'static dyn Log {
+        if INIT.is_completed() {
+            unsafe { LOGGER }
+        } else {
+            &DefaultLogger
+        }
+    }
+}
+
+// Without `std` this crate is built for the guest, which is single threaded.
+#[cfg(not(feature = "std"))]
+mod registry {
+    use core::cell::Cell;
+
+    use super::{DefaultLogger, Log};
+
+    struct Slot(Cell<Option<&'static dyn Log>>);
+
+    unsafe impl Sync for Slot {}
+
+    static LOGGER: Slot = Slot(Cell::new(None));
+
+    pub(super) fn set(logger: &'static dyn Log) -> bool {
+        if LOGGER.0.get().is_some() {
+            return false;
+        }
+        LOGGER.0.set(Some(logger));
+        true
+    }
+
+    pub(super) fn get() -> &'static dyn Log {
+        LOGGER.0.get().unwrap_or(&DefaultLogger)
+    }
+}
+
+/// Sets the logger that receives messages logged with [log]. This can only
+/// be done once. Until then, host builds forward messages to the `log` crate
+/// and guest builds discard them.
+pub fn set_logger(logger: &'static dyn Log) -> Result<(), SetLoggerError> {
+    if registry::set(logger) {
+        Ok(())
+    } else {
+        Err(SetLoggerError(()))
+    }
 }
 
 pub fn logger() -> &'static dyn Log {
-    unsafe { LOGGER }
+    registry::get()
 }
 
-pub fn log(msg: &str) {
-    logger().log(msg);
+pub fn log(level: Level, target: &str, msg: &str) {
+    logger().log(level, target, msg);
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::format;
+
+    use super::{log, set_logger, Level, Log};
+
+    struct TestLogger;
+
+    impl Log for TestLogger {
+        fn log(&self, level: Level, target: &str, msg: &str) {
+            assert_eq!(level, Level::Warn);
+            assert_eq!(target, "test");
+            assert_eq!(msg, "message");
+        }
+    }
+
+    static LOGGER: TestLogger = TestLogger;
+
+    #[test]
+    fn set_once() {
+        set_logger(&LOGGER).unwrap();
+        assert!(set_logger(&LOGGER).is_err());
+        log(Level::Warn, "test", "message");
+    }
+
+    #[test]
+    fn level() {
+        assert!(Level::Error < Level::Trace);
+        assert_eq!(format!("[{:<5}]", Level::Info), "[INFO ]");
+    }
 }
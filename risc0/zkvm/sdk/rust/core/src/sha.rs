@@ -0,0 +1,175 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SHA-256 in software, for code that cannot use the accelerator.
+
+use crate::{Digest, DIGEST_WORDS};
+
+/// The SHA-256 state before any input.
+pub const INIT: [u32; DIGEST_WORDS] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const ROUND_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// Runs the SHA-256 compression function over one block of big-endian words.
+pub fn compress(state: &mut [u32; DIGEST_WORDS], chunk: &[u32; 16]) {
+    let mut w = [0u32; 64];
+    w[..16].copy_from_slice(chunk);
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = s1
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 16]);
+    }
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for i in 0..64 {
+        let ep1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = h
+            .wrapping_add(ep1)
+            .wrapping_add(ch)
+            .wrapping_add(ROUND_K[i])
+            .wrapping_add(w[i]);
+        let ep0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = ep0.wrapping_add(maj);
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+    for (word, x) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *word = word.wrapping_add(x);
+    }
+}
+
+/// A standard SHA-256 over a stream of big-endian words.
+#[derive(Clone)]
+pub struct Sha256 {
+    state: [u32; DIGEST_WORDS],
+    block: [u32; 16],
+    len_words: usize,
+}
+
+impl Sha256 {
+    pub fn new() -> Self {
+        Sha256 {
+            state: INIT,
+            block: [0; 16],
+            len_words: 0,
+        }
+    }
+
+    pub fn update<I: IntoIterator<Item = u32>>(&mut self, words: I) {
+        for word in words {
+            let cur = self.len_words % 16;
+            self.block[cur] = word;
+            self.len_words += 1;
+            if cur == 15 {
+                compress(&mut self.state, &self.block);
+            }
+        }
+    }
+
+    pub fn finalize(mut self) -> Digest {
+        let mut cur = self.len_words % 16;
+        self.block[cur] = 0x80000000;
+        cur += 1;
+        self.block[cur..].fill(0);
+        if cur > 14 {
+            compress(&mut self.state, &self.block);
+            self.block.fill(0);
+        }
+        let bits = self.len_words as u64 * 32;
+        self.block[14] = (bits >> 32) as u32;
+        self.block[15] = bits as u32;
+        compress(&mut self.state, &self.block);
+        Digest::new(self.state)
+    }
+}
+
+impl Default for Sha256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sha256;
+    use crate::Digest;
+
+    fn hash(bytes: &[u8]) -> Digest {
+        let mut sha = Sha256::new();
+        sha.update(
+            bytes
+                .chunks_exact(4)
+                .map(|word| u32::from_be_bytes(word.try_into().unwrap())),
+        );
+        sha.finalize()
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            hash(b"").as_slice(),
+            &[
+                0xe3b0c442, 0x98fc1c14, 0x9afbf4c8, 0x996fb924, 0x27ae41e4, 0x649b934c, 0xa495991b,
+                0x7852b855,
+            ]
+        );
+    }
+
+    #[test]
+    fn padding_in_extra_block() {
+        // 56 bytes leave no room for the length in the last block.
+        assert_eq!(
+            hash(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq").as_slice(),
+            &[
+                0x248d6a61, 0xd20638b8, 0xe5c02693, 0x0c3e6039, 0xa33ce459, 0x64ff2167, 0xf6ecedd4,
+                0x19db06c1,
+            ]
+        );
+    }
+
+    #[test]
+    fn split_updates() {
+        let bytes = [0x5au8; 200];
+        let mut sha = Sha256::new();
+        for chunk in bytes.chunks(52) {
+            sha.update(
+                chunk
+                    .chunks_exact(4)
+                    .map(|word| u32::from_be_bytes(word.try_into().unwrap())),
+            );
+        }
+        assert_eq!(sha.finalize(), hash(&bytes));
+    }
+}
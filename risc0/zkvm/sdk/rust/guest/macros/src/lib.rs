@@ -0,0 +1,333 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `#[zkvm_guest::main]` attribute. This is implemented without `syn` so
+//! that guests do not pull a parser into their build; it only inspects the
+//! function signature as far as it needs to.
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+/// What the entry point returns, and so what gets committed.
+#[derive(Debug, Eq, PartialEq)]
+enum Return {
+    /// Nothing is committed.
+    Unit,
+    /// The value is committed.
+    Value,
+    /// The `Ok` value is committed, and an `Err` fails the guest.
+    Result,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct Entry {
+    name: String,
+    has_input: bool,
+    ret: Return,
+}
+
+/// The parts of a token that the parser looks at. `proc_macro` tokens can
+/// only be created while a macro is being expanded, so they are converted to
+/// these first, which keeps the parser testable.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Token {
+    Ident(String),
+    Punct(char),
+    Group(Delimiter, Vec<Token>),
+    Literal,
+}
+
+/// Declares the entry point of a guest. The function takes at most one
+/// argument, which is read from the input, and its return value is committed
+/// to the journal:
+///
+/// ```ignore
+/// #[zkvm_guest::main]
+/// fn main(params: RoundParams) -> RoundCommit {
+///     ...
+/// }
+/// ```
+///
+/// A function returning `Result<T, E>` commits `T` on success. An error fails
+/// the guest with the `Debug` representation of `E`, which the host reports
+/// as `ExceptionKind::GuestFault`. The return type must be spelled `Result`
+/// for this to apply; aliases of `Result` are committed as plain values.
+#[proc_macro_attribute]
+pub fn main(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return compile_error("#[zkvm_guest::main] takes no arguments");
+    }
+    match Entry::parse(&Token::parse(item.clone())) {
+        Ok(entry) => {
+            let mut out = item;
+            out.extend(entry.expand());
+            out
+        }
+        Err(msg) => compile_error(msg),
+    }
+}
+
+impl Entry {
+    fn parse(tokens: &[Token]) -> Result<Self, &'static str> {
+        let fn_pos = tokens
+            .iter()
+            .position(|token| is_ident(token, "fn"))
+            .ok_or("#[zkvm_guest::main] must be applied to a function")?;
+        if tokens[..fn_pos]
+            .iter()
+            .any(|token| is_ident(token, "async"))
+        {
+            return Err("Entry points may not be async");
+        }
+        let name = match tokens.get(fn_pos + 1) {
+            Some(Token::Ident(name)) => name.clone(),
+            _ => return Err("Expected a function name"),
+        };
+        let has_input = match tokens.get(fn_pos + 2) {
+            Some(Token::Group(Delimiter::Parenthesis, args)) => match count_args(args) {
+                0 => false,
+                1 => true,
+                _ => return Err("Entry points take at most one argument"),
+            },
+            _ => return Err("Entry points may not be generic"),
+        };
+        let ret = parse_return(&tokens[fn_pos + 3..]);
+        Ok(Entry {
+            name,
+            has_input,
+            ret,
+        })
+    }
+
+    fn expand(&self) -> TokenStream {
+        let call = if self.has_input {
+            format!("{}(::zkvm_guest::env::read())", self.name)
+        } else {
+            format!("{}()", self.name)
+        };
+        let body = match self.ret {
+            Return::Unit => format!("{};", call),
+            Return::Value => format!("::zkvm_guest::env::commit(&{});", call),
+            Return::Result => format!(
+                "match {} {{
+                    ::core::result::Result::Ok(value) => ::zkvm_guest::env::commit(&value),
+                    ::core::result::Result::Err(err) => ::zkvm_guest::__fail(&err),
+                }}",
+                call
+            ),
+        };
+        format!("#[no_mangle] fn __main() {{ {} }}", body)
+            .parse()
+            .unwrap()
+    }
+}
+
+impl Token {
+    fn parse(stream: TokenStream) -> Vec<Token> {
+        stream
+            .into_iter()
+            .map(|token| match token {
+                TokenTree::Ident(ident) => Token::Ident(ident.to_string()),
+                TokenTree::Punct(punct) => Token::Punct(punct.as_char()),
+                TokenTree::Group(group) => {
+                    Token::Group(group.delimiter(), Token::parse(group.stream()))
+                }
+                TokenTree::Literal(_) => Token::Literal,
+            })
+            .collect()
+    }
+}
+
+/// Counts the comma-separated arguments in the tokens of an argument list.
+fn count_args(tokens: &[Token]) -> usize {
+    // Commas also separate the generic arguments of argument types, which are
+    // not grouped into tokens of their own.
+    let mut depth = 0usize;
+    let mut args = 0;
+    let mut empty = true;
+    let mut prev = None;
+    for token in tokens {
+        match token {
+            Token::Punct('<') => depth += 1,
+            // Not the `->` of a function type.
+            Token::Punct('>') if prev != Some(&Token::Punct('-')) => {
+                depth = depth.saturating_sub(1)
+            }
+            Token::Punct(',') if depth == 0 => {
+                args += 1;
+                empty = true;
+                prev = Some(token);
+                continue;
+            }
+            _ => {}
+        }
+        empty = false;
+        prev = Some(token);
+    }
+    // Allow a trailing comma.
+    if empty {
+        args
+    } else {
+        args + 1
+    }
+}
+
+/// Classifies the return type from the tokens following the argument list.
+fn parse_return(tokens: &[Token]) -> Return {
+    let ty = match tokens {
+        [Token::Punct('-'), Token::Punct('>'), ty @ ..] => ty,
+        _ => return Return::Unit,
+    };
+    let end = ty
+        .iter()
+        .position(|token| match token {
+            Token::Group(delimiter, _) => *delimiter == Delimiter::Brace,
+            token => is_ident(token, "where"),
+        })
+        .unwrap_or(ty.len());
+    let ty = &ty[..end];
+    if let [Token::Group(Delimiter::Parenthesis, tuple)] = ty {
+        if tuple.is_empty() {
+            return Return::Unit;
+        }
+    }
+    // The last path segment before any generic arguments names the type.
+    let last = ty
+        .iter()
+        .take_while(|token| **token != Token::Punct('<'))
+        .filter_map(|token| match token {
+            Token::Ident(ident) => Some(ident.as_str()),
+            _ => None,
+        })
+        .last();
+    match last {
+        Some("Result") => Return::Result,
+        _ => Return::Value,
+    }
+}
+
+fn is_ident(token: &Token, name: &str) -> bool {
+    matches!(token, Token::Ident(ident) if ident == name)
+}
+
+fn compile_error(msg: &str) -> TokenStream {
+    format!("::core::compile_error!({:?});", msg)
+        .parse()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{iter::Peekable, str::Chars};
+
+    use super::*;
+
+    // Splits `src` into tokens the way the compiler does, for the syntax used
+    // below.
+    fn tokens(src: &str) -> Vec<Token> {
+        fn parse(chars: &mut Peekable<Chars>) -> Vec<Token> {
+            let mut tokens = Vec::new();
+            while let Some(c) = chars.next() {
+                let token = match c {
+                    ')' | ']' | '}' => break,
+                    '(' => Token::Group(Delimiter::Parenthesis, parse(chars)),
+                    '[' => Token::Group(Delimiter::Bracket, parse(chars)),
+                    '{' => Token::Group(Delimiter::Brace, parse(chars)),
+                    c if c.is_whitespace() => continue,
+                    c if c.is_ascii_digit() => {
+                        while chars.next_if(char::is_ascii_digit).is_some() {}
+                        Token::Literal
+                    }
+                    c if c.is_alphabetic() || c == '_' => {
+                        let mut ident = c.to_string();
+                        while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+                            ident.push(c);
+                        }
+                        Token::Ident(ident)
+                    }
+                    c => Token::Punct(c),
+                };
+                tokens.push(token);
+            }
+            tokens
+        }
+        parse(&mut src.chars().peekable())
+    }
+
+    #[test]
+    fn parse_return_types() {
+        for (src, ret) in [
+            ("{}", Return::Unit),
+            ("-> () {}", Return::Unit),
+            ("-> u32 {}", Return::Value),
+            ("-> (u32, u32) {}", Return::Value),
+            ("-> Vec<Result<u32, E>> {}", Return::Value),
+            ("-> MyResult where T: Clone {}", Return::Value),
+            ("-> Result<u32, &'static str> {}", Return::Result),
+            ("-> core::result::Result<(), E> {}", Return::Result),
+        ] {
+            assert_eq!(parse_return(&tokens(src)), ret, "{}", src);
+        }
+    }
+
+    #[test]
+    fn parse_entry() {
+        assert_eq!(
+            Entry::parse(&tokens("#[inline] pub fn run(params: Params) -> u32 {}")),
+            Ok(Entry {
+                name: "run".to_string(),
+                has_input: true,
+                ret: Return::Value,
+            })
+        );
+        assert_eq!(
+            Entry::parse(&tokens("fn main() {}")),
+            Ok(Entry {
+                name: "main".to_string(),
+                has_input: false,
+                ret: Return::Unit,
+            })
+        );
+        for src in [
+            "fn main(map: BTreeMap<u32, u32>) {}",
+            "fn main(pair: (u32, u32),) {}",
+            "fn main(f: Box<dyn Fn(u32, u32) -> u32>) {}",
+        ] {
+            assert_eq!(
+                Entry::parse(&tokens(src)).map(|e| e.has_input),
+                Ok(true),
+                "{}",
+                src
+            );
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        for (src, err) in [
+            (
+                "struct Main;",
+                "#[zkvm_guest::main] must be applied to a function",
+            ),
+            ("async fn main() {}", "Entry points may not be async"),
+            ("pub async fn main() {}", "Entry points may not be async"),
+            ("fn main<T>(input: T) {}", "Entry points may not be generic"),
+            (
+                "fn main(a: u32, b: u32) {}",
+                "Entry points take at most one argument",
+            ),
+        ] {
+            assert_eq!(Entry::parse(&tokens(src)), Err(err), "{}", src);
+        }
+    }
+}
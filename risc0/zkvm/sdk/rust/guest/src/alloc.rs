@@ -15,14 +15,17 @@
 use core::{
     alloc::{GlobalAlloc, Layout},
     cell::UnsafeCell,
+    ptr,
 };
 
-use crate::{_fault, align_up, REGION_HEAP_END, REGION_HEAP_START, WORD_SIZE};
+use zkvm_platform::WORD_SIZE;
+
+use crate::{align_up, memory};
 
 // Bump pointer allocator for *single* core systems
 struct BumpPointerAlloc {
     head: UnsafeCell<usize>,
-    end: usize,
+    end: UnsafeCell<usize>,
 }
 unsafe impl Sync for BumpPointerAlloc {}
 
@@ -33,8 +36,9 @@ unsafe impl GlobalAlloc for BumpPointerAlloc {
         // move start up to the next alignment boundary
         let alloc_start = align_up(*head, WORD_SIZE);
         let alloc_end = alloc_start.checked_add(layout.size()).unwrap();
-        if alloc_end > self.end {
-            _fault();
+        if alloc_end > *self.end.get() {
+            // Reported by `alloc_fault`.
+            ptr::null_mut()
         } else {
             *head = alloc_end;
             alloc_start as *mut u8
@@ -48,11 +52,22 @@ unsafe impl GlobalAlloc for BumpPointerAlloc {
 
 #[global_allocator]
 static HEAP: BumpPointerAlloc = BumpPointerAlloc {
-    head: UnsafeCell::new(REGION_HEAP_START),
-    end: REGION_HEAP_END,
+    head: UnsafeCell::new(0),
+    end: UnsafeCell::new(0),
 };
 
+// Reading the bounds of the heap from the linker script is not a constant
+// expression, so nothing can be allocated until this is called.
+pub(crate) fn init() {
+    let heap = memory::heap();
+    unsafe {
+        *HEAP.head.get() = heap.start();
+        *HEAP.end.get() = heap.end();
+    }
+}
+
 #[alloc_error_handler]
-unsafe fn alloc_fault(_layout: Layout) -> ! {
-    _fault()
+fn alloc_fault(layout: Layout) -> ! {
+    // Panicking does not allocate, so this is reported like any other panic.
+    panic!("Out of memory: failed to allocate {} bytes", layout.size())
 }
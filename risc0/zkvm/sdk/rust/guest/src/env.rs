@@ -12,23 +12,38 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use core::{cell::UnsafeCell, mem::MaybeUninit, slice};
+use _alloc::vec::Vec;
+use core::{
+    cell::UnsafeCell,
+    mem::{self, MaybeUninit},
+    slice,
+};
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
     gpio::{IoDescriptor, GPIO_COMMIT, GPIO_DESC_IO, GPIO_WRITE},
-    sha::{self, digest_commit_into},
-    REGION_COMMIT_LEN, REGION_COMMIT_START, REGION_INPUT_LEN, REGION_INPUT_START,
-    REGION_OUTPUT_LEN, REGION_OUTPUT_START, WORD_SIZE,
+    memory,
+    sha::{self, digest_commit_into, padded_size},
 };
-use zkvm_serde::{Deserializer, Serializer, Slice};
+use zkvm_core::sha::Sha256;
+use zkvm_platform::WORD_SIZE;
+use zkvm_serde::{AllocVec, Deserializer, Serializer, Slice};
 
 struct Env {
     input: Deserializer<'static>,
-    output: Serializer<Slice<'static>>,
+    // Private output is not part of the proof, so each write is staged on
+    // the heap rather than in write-once memory, where it could never be
+    // overwritten. The buffer is reused, so only the largest write has to
+    // fit. Commits past the end of the commit region are staged here too.
+    output: Vec<u32>,
     commit: Serializer<Slice<'static>>,
     commit_len: usize,
+    // Set once the journal outgrows the commit region. The accelerator only
+    // hashes memory as it is when the guest halts, so from then on commits
+    // are hashed in software as they happen, and handed to the host right
+    // away instead of at the end.
+    journal: Option<Sha256>,
 }
 
 struct Once<T> {
@@ -73,6 +88,13 @@ pub fn read<T: Deserialize<'static>>() -> T {
     ENV.get().read()
 }
 
+/// Like [read], but returns an error instead of panicking if the input does
+/// not decode as a `T`. The input is then left as it was, so it can be read
+/// again as something else.
+pub fn try_read<T: Deserialize<'static>>() -> Result<T, zkvm_serde::Error> {
+    ENV.get().try_read()
+}
+
 pub fn write<T: Serialize>(data: &T) {
     ENV.get().write(data);
 }
@@ -85,63 +107,105 @@ impl Env {
     fn new() -> Self {
         Env {
             input: Deserializer::new(unsafe {
-                slice::from_raw_parts(REGION_INPUT_START as _, REGION_INPUT_LEN / WORD_SIZE)
+                slice::from_raw_parts(memory::input().start() as _, memory::input().len_words())
             }),
-            output: Serializer::new(Slice::new(unsafe {
-                slice::from_raw_parts_mut(REGION_OUTPUT_START as _, REGION_OUTPUT_LEN / WORD_SIZE)
-            })),
+            output: Vec::new(),
             commit: Serializer::new(Slice::new(unsafe {
-                slice::from_raw_parts_mut(REGION_COMMIT_START as _, REGION_COMMIT_LEN / WORD_SIZE)
+                slice::from_raw_parts_mut(
+                    memory::commit().start() as _,
+                    memory::commit().len_words(),
+                )
             })),
             commit_len: 0,
+            journal: None,
         }
     }
 
     fn read<T: Deserialize<'static>>(&mut self) -> T {
-        T::deserialize(&mut self.input).unwrap()
+        self.try_read().unwrap()
+    }
+
+    fn try_read<T: Deserialize<'static>>(&mut self) -> Result<T, zkvm_serde::Error> {
+        let mut input = self.input.clone();
+        let value = T::deserialize(&mut input)?;
+        self.input = input;
+        Ok(value)
     }
 
     fn write<T: Serialize>(&mut self, data: &T) {
-        data.serialize(&mut self.output).unwrap();
-        let buf = self.output.release().unwrap();
-        unsafe {
-            GPIO_DESC_IO.write_volatile(IoDescriptor {
-                size: buf.len() * WORD_SIZE,
-                addr: buf.as_ptr() as usize,
-            });
-            GPIO_WRITE.write_volatile(GPIO_DESC_IO);
-        }
+        let buf = self.stage(data);
+        send(GPIO_WRITE, &buf);
+        self.output = buf;
     }
 
     fn commit<T: Serialize>(&mut self, data: &T) {
-        data.serialize(&mut self.commit).unwrap();
-        let buf = self.commit.release().unwrap();
-        self.commit_len += buf.len();
-        let len_bytes = buf.len() * WORD_SIZE;
-        unsafe {
-            GPIO_DESC_IO.write_volatile(IoDescriptor {
-                size: len_bytes,
-                addr: buf.as_ptr() as usize,
-            });
-            GPIO_WRITE.write_volatile(GPIO_DESC_IO);
+        if self.journal.is_none() {
+            match data.serialize(&mut self.commit) {
+                Err(zkvm_serde::Error::SerializeBufferFull) => self.stream_journal(),
+                result => {
+                    result.unwrap();
+                    let buf = self.commit.release().unwrap();
+                    self.commit_len += buf.len();
+                    send(GPIO_WRITE, buf);
+                    return;
+                }
+            }
+        }
+        let buf = self.stage(data);
+        send(GPIO_WRITE, &buf);
+        send(GPIO_COMMIT, &buf);
+        if let Some(journal) = &mut self.journal {
+            journal.update(buf.iter().map(|word| u32::from_be(*word)));
         }
+        self.commit_len += buf.len();
+        self.output = buf;
+    }
+
+    // Serializes `data` into the reused heap buffer, which the caller has to
+    // put back.
+    fn stage<T: Serialize>(&mut self, data: &T) -> Vec<u32> {
+        let mut buf = mem::take(&mut self.output);
+        buf.clear();
+        let mut serializer = Serializer::new(AllocVec(buf));
+        data.serialize(&mut serializer).unwrap();
+        serializer.release().unwrap()
+    }
+
+    // Hashes what the commit region holds so far and hands it to the host,
+    // so that the journal can continue past the end of the region.
+    fn stream_journal(&mut self) {
+        let slice: &[u32] =
+            unsafe { slice::from_raw_parts(memory::commit().start() as _, self.commit_len) };
+        let mut journal = Sha256::new();
+        journal.update(slice.iter().map(|word| u32::from_be(*word)));
+        send(GPIO_COMMIT, slice);
+        self.journal = Some(journal);
     }
 
     fn finalize(&mut self, result: *mut usize) {
         let len_words = self.commit_len;
         let len_bytes = len_words * WORD_SIZE;
+        // The accelerator pads the journal in place.
+        if self.journal.is_none()
+            && len_words > 8
+            && padded_size(len_bytes) > memory::commit().len_words() * WORD_SIZE
+        {
+            self.stream_journal();
+        }
+        if let Some(journal) = self.journal.take() {
+            for (i, word) in journal.finalize().as_slice().iter().enumerate() {
+                unsafe { result.add(i).write_volatile(*word as usize) };
+            }
+            unsafe { result.add(8).write_volatile(len_bytes) };
+            sha::finalize();
+            return;
+        }
         let slice: &mut [u32] = unsafe {
-            slice::from_raw_parts_mut(REGION_COMMIT_START as _, REGION_COMMIT_LEN / WORD_SIZE)
+            slice::from_raw_parts_mut(memory::commit().start() as _, memory::commit().len_words())
         };
 
         // Write the full data out to the host
-        unsafe {
-            GPIO_DESC_IO.write_volatile(IoDescriptor {
-                size: len_bytes,
-                addr: slice.as_ptr() as usize,
-            });
-            GPIO_COMMIT.write_volatile(GPIO_DESC_IO);
-        }
+        send(GPIO_COMMIT, &slice[..len_words]);
 
         // If the total proof message is small (<= 32 bytes), return it directly
         // from the proof, otherwise SHA it and return the hash.
@@ -163,3 +227,13 @@ impl Env {
         sha::finalize();
     }
 }
+
+fn send(gpio: *mut *const IoDescriptor, buf: &[u32]) {
+    unsafe {
+        GPIO_DESC_IO.write_volatile(IoDescriptor {
+            size: buf.len() * WORD_SIZE,
+            addr: buf.as_ptr() as usize,
+        });
+        gpio.write_volatile(GPIO_DESC_IO);
+    }
+}
@@ -12,15 +12,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub(crate) const GPIO_SHA: *mut *const SHADescriptor = 0x001C_0000 as _;
-pub(crate) const GPIO_WRITE: *mut *const IoDescriptor = 0x001C_0004 as _;
-pub(crate) const GPIO_COMMIT: *mut *const IoDescriptor = 0x001C_0008 as _;
-pub(crate) const GPIO_FAULT: *mut *const FaultDescriptor = 0x001C_000C as _;
-pub(crate) const GPIO_LOG: *mut *const LogDescriptor = 0x001C_0010 as _;
+use zkvm_platform::io;
 
-pub(crate) const GPIO_DESC_IO: *mut IoDescriptor = 0x001D_0000 as _;
-pub(crate) const GPIO_DESC_LOG: *mut LogDescriptor = 0x001D_0000 as _;
-pub(crate) const GPIO_DESC_FAULT: *mut FaultDescriptor = 0x001D_0000 as _;
+pub(crate) const GPIO_SHA: *mut *const SHADescriptor = io::GPIO_SHA as _;
+pub(crate) const GPIO_WRITE: *mut *const IoDescriptor = io::GPIO_WRITE as _;
+pub(crate) const GPIO_COMMIT: *mut *const IoDescriptor = io::GPIO_COMMIT as _;
+pub(crate) const GPIO_FAULT: *mut *const FaultDescriptor = io::GPIO_FAULT as _;
+pub(crate) const GPIO_LOG: *mut *const LogDescriptor = io::GPIO_LOG as _;
+#[cfg(feature = "log")]
+pub(crate) const GPIO_GET_LOG_LEVEL: *mut *const GetLogLevelDescriptor =
+    io::GPIO_GET_LOG_LEVEL as _;
+pub(crate) const GPIO_GET_CYCLE: *mut *const GetCycleDescriptor = io::GPIO_GET_CYCLE as _;
+pub(crate) const GPIO_SPAN: *mut *const SpanDescriptor = io::GPIO_SPAN as _;
+
+pub(crate) const GPIO_DESC_IO: *mut IoDescriptor = io::GPIO_DESC as _;
+pub(crate) const GPIO_DESC_LOG: *mut LogDescriptor = io::GPIO_DESC as _;
+pub(crate) const GPIO_DESC_FAULT: *mut FaultDescriptor = io::GPIO_DESC as _;
+#[cfg(feature = "log")]
+pub(crate) const GPIO_DESC_GET_LOG_LEVEL: *mut GetLogLevelDescriptor = io::GPIO_DESC as _;
+pub(crate) const GPIO_DESC_GET_CYCLE: *mut GetCycleDescriptor = io::GPIO_DESC as _;
+pub(crate) const GPIO_DESC_SPAN: *mut SpanDescriptor = io::GPIO_DESC as _;
 
 #[repr(C)]
 pub(crate) struct IoDescriptor {
@@ -31,6 +42,10 @@ pub(crate) struct IoDescriptor {
 #[repr(C)]
 pub(crate) struct FaultDescriptor {
     pub addr: usize,
+    // Zero if the location is unknown.
+    pub file: usize,
+    pub line: usize,
+    pub column: usize,
 }
 
 #[repr(C)]
@@ -38,6 +53,27 @@ pub(crate) struct LogDescriptor {
     pub addr: usize,
 }
 
+#[cfg(feature = "log")]
+#[repr(C)]
+pub(crate) struct GetLogLevelDescriptor {
+    // Where the host stores the level, in the order of `log::LevelFilter`.
+    pub addr: usize,
+}
+
+#[repr(C)]
+pub(crate) struct GetCycleDescriptor {
+    // Where the host stores the current cycle.
+    pub addr: usize,
+}
+
+#[repr(C)]
+pub(crate) struct SpanDescriptor {
+    pub name: usize,
+    pub len: usize,
+    // Non-zero when entering the span.
+    pub enter: usize,
+}
+
 #[repr(C)]
 pub(crate) struct SHADescriptor {
     pub type_count: usize,
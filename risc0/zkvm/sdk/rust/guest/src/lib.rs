@@ -15,63 +15,43 @@
 #![no_std]
 #![feature(alloc_error_handler)]
 #![feature(new_uninit)]
+#![feature(panic_info_message)]
 
 extern crate alloc as _alloc;
 
 mod alloc;
 pub mod env;
 mod gpio;
+#[cfg(feature = "log")]
+mod logger;
+mod memory;
+mod panic;
+mod print;
+pub mod profile;
 pub mod sha;
 
-use core::{mem, panic::PanicInfo, ptr};
+/// Declares the entry point of a guest, reading its input and committing its
+/// result. See [zkvm_guest_macros::main].
+pub use zkvm_guest_macros::main;
 
-use gpio::{FaultDescriptor, LogDescriptor, GPIO_DESC_FAULT, GPIO_DESC_LOG, GPIO_FAULT, GPIO_LOG};
-use zkvm_core::{set_logger, Log};
+#[doc(hidden)]
+pub use panic::__fail;
+pub use panic::{set_panic_hook, take_panic_hook, PanicHook, PANIC_MESSAGE_CAPACITY};
+#[doc(hidden)]
+pub use print::{__eprint, __print};
 
-const REGION_SIZE_256KB: usize = 256 * 1024;
-// const REGION_SIZE_512KB: usize = 0x0008_0000;
-const REGION_SIZE_1MB: usize = 1024 * 1024;
+use core::ptr;
 
-const REGION_HEAP_START: usize = 0x0008_0000;
-const REGION_HEAP_LEN: usize = REGION_SIZE_1MB + REGION_SIZE_256KB;
-const REGION_HEAP_END: usize = REGION_HEAP_START + REGION_HEAP_LEN;
+use zkvm_core::{set_logger, Level, Log};
 
-const REGION_SHA_START: usize = 0x0030_0000;
-// const REGION_SHA_LEN: usize = REGION_SIZE_256KB;
-// const REGION_SHA_END: usize = REGION_SHA_START + REGION_SHA_LEN;
-
-const REGION_INPUT_START: usize = 0x0018_0000;
-const REGION_INPUT_LEN: usize = REGION_SIZE_256KB;
-// const REGION_INPUT_END: usize = REGION_INPUT_START + REGION_INPUT_LEN;
-
-const REGION_OUTPUT_START: usize = 0x0034_0000;
-const REGION_OUTPUT_LEN: usize = REGION_SIZE_256KB;
-
-const REGION_COMMIT_START: usize = 0x0038_0000;
-const REGION_COMMIT_LEN: usize = REGION_SIZE_256KB;
-
-const WORD_SIZE: usize = mem::size_of::<u32>();
+// Cargo builds have no `crt0` target to link against, so assemble it here.
+#[cfg(all(feature = "crt0", target_arch = "riscv32"))]
+core::arch::global_asm!(include_str!("../../../cpp/guest/crt0.s"));
 
 extern "C" {
     fn _fault() -> !;
 }
 
-#[panic_handler]
-unsafe fn panic_fault(panic_info: &PanicInfo<'static>) -> ! {
-    let msg = _alloc::format!("{}\0", panic_info);
-
-    GPIO_DESC_FAULT.write_volatile(FaultDescriptor {
-        // addr: "panic\0".as_ptr() as usize,
-        addr: msg.as_ptr() as usize,
-    });
-    // A compliant host should fault when it receives this descriptor.
-    GPIO_FAULT.write_volatile(GPIO_DESC_FAULT);
-
-    // As a fallback for uncompliant hosts, force an unaligned write, which causes a
-    // fault within the Risc0 VM.
-    _fault()
-}
-
 #[macro_export]
 macro_rules! entry {
     ($path:path) => {
@@ -87,14 +67,12 @@ macro_rules! entry {
 struct Logger;
 
 impl Log for Logger {
-    fn log(&self, msg: &str) {
-        let msg = _alloc::format!("{}\0", msg);
-        unsafe {
-            GPIO_DESC_LOG.write_volatile(LogDescriptor {
-                addr: msg.as_ptr() as usize,
-            });
-            GPIO_LOG.write_volatile(GPIO_DESC_LOG);
+    fn log(&self, level: Level, target: &str, msg: &str) {
+        #[cfg(feature = "log")]
+        if !logger::enabled(level) {
+            return;
         }
+        print::log(format_args!("{:<5} {}: {}", level, target, msg));
     }
 }
 
@@ -108,7 +86,10 @@ unsafe extern "C" fn __start(result: *mut usize) {
     }
     ptr::write_bytes(&mut __bss_begin as *mut u8, 0, __bss_size);
 
-    set_logger(&LOGGER);
+    alloc::init();
+    let _ = set_logger(&LOGGER);
+    #[cfg(feature = "log")]
+    logger::init();
 
     env::init();
 
@@ -117,6 +98,7 @@ unsafe extern "C" fn __start(result: *mut usize) {
     }
     __main();
 
+    print::flush();
     env::finalize(result);
 }
 
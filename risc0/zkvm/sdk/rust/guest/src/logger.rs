@@ -0,0 +1,86 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implements the `log` crate facade, so that records logged by the guest and
+//! the crates it uses are sent to the host. The host chooses the maximum
+//! level, and records above it are discarded without being formatted.
+
+use _alloc::boxed::Box;
+use core::mem::MaybeUninit;
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+use crate::{
+    gpio::{GetLogLevelDescriptor, GPIO_DESC_GET_LOG_LEVEL, GPIO_GET_LOG_LEVEL},
+    print,
+};
+
+struct Logger;
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            print::log(format_args!(
+                "{:<5} {}: {}",
+                record.level(),
+                record.target(),
+                record.args()
+            ));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: Logger = Logger;
+
+/// Whether messages logged with `zkvm_core::log` at `level` pass the level
+/// chosen by the host.
+pub(crate) fn enabled(level: zkvm_core::Level) -> bool {
+    level as usize <= log::max_level() as usize
+}
+
+pub(crate) fn init() {
+    // Safe because the guest is single threaded, and this runs before any
+    // guest code.
+    unsafe {
+        let _ = log::set_logger_racy(&LOGGER);
+    }
+    log::set_max_level(host_level());
+}
+
+fn host_level() -> LevelFilter {
+    // The host can only fill in memory the guest has never written to, so the
+    // level is received in a fresh heap allocation rather than on the stack.
+    let level: Box<MaybeUninit<usize>> = Box::new_uninit();
+    let level = unsafe {
+        GPIO_DESC_GET_LOG_LEVEL.write_volatile(GetLogLevelDescriptor {
+            addr: level.as_ptr() as usize,
+        });
+        GPIO_GET_LOG_LEVEL.write_volatile(GPIO_DESC_GET_LOG_LEVEL);
+        level.as_ptr().read_volatile()
+    };
+    match level {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Error,
+        2 => LevelFilter::Warn,
+        3 => LevelFilter::Info,
+        4 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The regions of guest memory this method was linked with. Methods can
+//! resize them, see `risc0/zkvm/platform/risc0.ld`, so they are read from the
+//! symbols the linker script defines rather than from [zkvm_platform::memory].
+
+use zkvm_platform::memory::Region;
+
+extern "C" {
+    static __heap_start: u8;
+    static __heap_end: u8;
+    static __input_start: u8;
+    static __input_end: u8;
+    static __commit_start: u8;
+    static __commit_end: u8;
+}
+
+// The address of a linker script symbol is its value.
+fn region(start: &u8, end: &u8) -> Region {
+    let start = start as *const u8 as usize;
+    Region::new(start, end as *const u8 as usize - start)
+}
+
+pub(crate) fn heap() -> Region {
+    unsafe { region(&__heap_start, &__heap_end) }
+}
+
+pub(crate) fn input() -> Region {
+    unsafe { region(&__input_start, &__input_end) }
+}
+
+pub(crate) fn commit() -> Region {
+    unsafe { region(&__commit_start, &__commit_end) }
+}
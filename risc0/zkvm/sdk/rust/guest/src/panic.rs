@@ -0,0 +1,203 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reports guest panics to the host. Nothing here allocates, so that panics
+//! raised when the heap is exhausted, or by the allocator itself, are still
+//! reported.
+
+use core::{
+    cell::UnsafeCell,
+    fmt::{self, Debug, Write},
+    panic::{Location, PanicInfo},
+};
+
+use crate::{
+    _fault,
+    gpio::{FaultDescriptor, GPIO_DESC_FAULT, GPIO_FAULT},
+    print,
+};
+
+/// The size of the buffer panic messages are formatted into, including the
+/// NUL terminator. Longer messages are truncated.
+pub const PANIC_MESSAGE_CAPACITY: usize = 1024;
+
+const PANIC_FILE_CAPACITY: usize = 256;
+
+/// Runs when the guest panics, before the panic is reported to the host.
+pub type PanicHook = fn(&PanicInfo);
+
+struct State {
+    hook: Option<PanicHook>,
+    // How many panics are being handled, to detect panics while panicking.
+    depth: usize,
+    msg: FixedBuf<PANIC_MESSAGE_CAPACITY>,
+    file: FixedBuf<PANIC_FILE_CAPACITY>,
+}
+
+struct Global(UnsafeCell<State>);
+
+// The guest is single threaded.
+unsafe impl Sync for Global {}
+
+static STATE: Global = Global(UnsafeCell::new(State {
+    hook: None,
+    depth: 0,
+    msg: FixedBuf::new(),
+    file: FixedBuf::new(),
+}));
+
+/// Borrows the panic state.
+///
+/// # Safety
+///
+/// The borrow must end before running any code that may panic or change the
+/// panic hook.
+unsafe fn state() -> &'static mut State {
+    &mut *STATE.0.get()
+}
+
+/// Formats into the panic message. Formatting runs `Display` and `Debug`
+/// impls, which may panic in turn, so the state is only borrowed for the
+/// duration of each `write_str`.
+struct Message;
+
+impl Write for Message {
+    fn write_str(&mut self, str: &str) -> fmt::Result {
+        unsafe { state() }.msg.write_str(str)
+    }
+}
+
+/// Installs a hook that runs when the guest panics, before the fault is
+/// raised, replacing any previous hook. If the hook itself panics, that panic
+/// is reported instead, without running the hook again.
+pub fn set_panic_hook(hook: PanicHook) {
+    unsafe { state() }.hook = Some(hook);
+}
+
+/// Removes the panic hook, returning it.
+pub fn take_panic_hook() -> Option<PanicHook> {
+    unsafe { state() }.hook.take()
+}
+
+#[panic_handler]
+fn panic_fault(panic_info: &PanicInfo) -> ! {
+    let (depth, hook) = {
+        let state = unsafe { state() };
+        state.depth += 1;
+        (state.depth, state.hook)
+    };
+    match depth {
+        1 => {
+            if let Some(hook) = hook {
+                hook(panic_info);
+            }
+        }
+        2 => {}
+        // Formatting the message of a nested panic panicked as well.
+        _ => unsafe { _fault() },
+    }
+    unsafe { state() }.msg.clear();
+    let _ = match panic_info.message() {
+        Some(args) => Message.write_fmt(*args),
+        None => Message.write_str("explicit panic"),
+    };
+    fault(panic_info.location())
+}
+
+/// Fails the guest with the error returned from an entry point declared with
+/// [main](crate::main).
+#[doc(hidden)]
+pub fn __fail<E: Debug>(err: &E) -> ! {
+    unsafe { state() }.msg.clear();
+    let _ = write!(Message, "{:?}", err);
+    fault(None)
+}
+
+/// Reports the panic message and the source location of the failure to the
+/// host and faults.
+fn fault(location: Option<&Location>) -> ! {
+    print::flush();
+    // Nothing below runs guest code, so the state stays borrowed until the end.
+    let state = unsafe { state() };
+    state.file.clear();
+    let (file, line, column) = match location {
+        Some(location) => {
+            let _ = state.file.write_str(location.file());
+            (
+                state.file.as_ptr() as usize,
+                location.line() as usize,
+                location.column() as usize,
+            )
+        }
+        None => (0, 0, 0),
+    };
+    unsafe {
+        GPIO_DESC_FAULT.write_volatile(FaultDescriptor {
+            addr: state.msg.as_ptr() as usize,
+            file,
+            line,
+            column,
+        });
+        // A compliant host should fault when it receives this descriptor.
+        GPIO_FAULT.write_volatile(GPIO_DESC_FAULT);
+
+        // As a fallback for uncompliant hosts, force an unaligned write, which causes a
+        // fault within the Risc0 VM.
+        _fault()
+    }
+}
+
+/// A NUL-terminated string in a fixed buffer. Writes that do not fit are
+/// truncated at a character boundary.
+struct FixedBuf<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> FixedBuf<N> {
+    const fn new() -> Self {
+        FixedBuf {
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.len = 0;
+        self.buf[0] = 0;
+    }
+
+    fn as_ptr(&self) -> *const u8 {
+        self.buf.as_ptr()
+    }
+}
+
+impl<const N: usize> Write for FixedBuf<N> {
+    fn write_str(&mut self, str: &str) -> fmt::Result {
+        let room = N - 1 - self.len;
+        let mut len = str.len().min(room);
+        while !str.is_char_boundary(len) {
+            len -= 1;
+        }
+        self.buf[self.len..self.len + len].copy_from_slice(&str.as_bytes()[..len]);
+        self.len += len;
+        self.buf[self.len] = 0;
+        if len == str.len() {
+            Ok(())
+        } else {
+            // Stop formatting once the buffer is full.
+            Err(fmt::Error)
+        }
+    }
+}
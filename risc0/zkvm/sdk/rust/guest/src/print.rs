@@ -0,0 +1,216 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Streams formatted text to the host log without allocating. Text is
+//! buffered a line at a time and each line is sent to the host as one log
+//! message. Lines longer than [LINE_CAPACITY] are split.
+
+use core::{
+    cell::UnsafeCell,
+    fmt::{self, Arguments, Write},
+};
+
+use crate::gpio::{LogDescriptor, GPIO_DESC_LOG, GPIO_LOG};
+
+/// The size of the buffer lines are formatted into, including the NUL
+/// terminator.
+const LINE_CAPACITY: usize = 256;
+
+struct Streams {
+    stdout: LineWriter,
+    stderr: LineWriter,
+    log: LineWriter,
+}
+
+struct Global(UnsafeCell<Streams>);
+
+// The guest is single threaded.
+unsafe impl Sync for Global {}
+
+static STREAMS: Global = Global(UnsafeCell::new(Streams {
+    stdout: LineWriter::new(),
+    stderr: LineWriter::new(),
+    log: LineWriter::new(),
+}));
+
+/// One of the buffered [Streams]. Formatting into a stream can run arbitrary
+/// `Display` impls, which may themselves print, so the line buffer is only
+/// borrowed for the duration of each `write_str`.
+#[derive(Clone, Copy)]
+enum Stream {
+    Stdout,
+    Stderr,
+    Log,
+}
+
+impl Stream {
+    /// Borrows the line buffer of this stream.
+    ///
+    /// # Safety
+    ///
+    /// The borrow must end before any code that may print runs, and before
+    /// the stream is borrowed again.
+    unsafe fn writer(self) -> &'static mut LineWriter {
+        let streams = STREAMS.0.get();
+        match self {
+            Stream::Stdout => &mut (*streams).stdout,
+            Stream::Stderr => &mut (*streams).stderr,
+            Stream::Log => &mut (*streams).log,
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write_str(&mut self, str: &str) -> fmt::Result {
+        // Safe because LineWriter only talks to the host, and never calls back
+        // into formatting code.
+        unsafe { self.writer() }.write_str(str)
+    }
+}
+
+/// Prints to the host log, see [print](crate::print).
+#[doc(hidden)]
+pub fn __print(args: Arguments) {
+    let _ = Stream::Stdout.write_fmt(args);
+}
+
+/// Prints to the host log, see [eprint](crate::eprint).
+#[doc(hidden)]
+pub fn __eprint(args: Arguments) {
+    let _ = Stream::Stderr.write_fmt(args);
+}
+
+/// Sends `args` to the host as a log message of its own, regardless of
+/// whether it ends with a newline.
+pub(crate) fn log(args: Arguments) {
+    let _ = Stream::Log.write_fmt(args);
+    unsafe { Stream::Log.writer() }.flush();
+}
+
+/// Sends any partial lines to the host.
+pub(crate) fn flush() {
+    unsafe {
+        Stream::Stdout.writer().flush();
+        Stream::Stderr.writer().flush();
+    }
+}
+
+struct LineWriter {
+    buf: [u8; LINE_CAPACITY],
+    len: usize,
+}
+
+impl LineWriter {
+    const fn new() -> Self {
+        LineWriter {
+            buf: [0; LINE_CAPACITY],
+            len: 0,
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.len != 0 {
+            self.send();
+        }
+    }
+
+    fn send(&mut self) {
+        self.buf[self.len] = 0;
+        unsafe {
+            GPIO_DESC_LOG.write_volatile(LogDescriptor {
+                addr: self.buf.as_ptr() as usize,
+            });
+            GPIO_LOG.write_volatile(GPIO_DESC_LOG);
+        }
+        self.len = 0;
+    }
+
+    fn push(&mut self, mut str: &str) {
+        while !str.is_empty() {
+            let room = LINE_CAPACITY - 1 - self.len;
+            if str.len() <= room {
+                self.buf[self.len..self.len + str.len()].copy_from_slice(str.as_bytes());
+                self.len += str.len();
+                return;
+            }
+            // Split at a character boundary so that each message is valid UTF-8.
+            let mut len = room;
+            while !str.is_char_boundary(len) {
+                len -= 1;
+            }
+            self.buf[self.len..self.len + len].copy_from_slice(&str.as_bytes()[..len]);
+            self.len += len;
+            self.send();
+            str = &str[len..];
+        }
+    }
+}
+
+impl Write for LineWriter {
+    fn write_str(&mut self, str: &str) -> fmt::Result {
+        let mut lines = str.split('\n');
+        if let Some(line) = lines.next() {
+            self.push(line);
+        }
+        for line in lines {
+            self.send();
+            self.push(line);
+        }
+        Ok(())
+    }
+}
+
+/// Prints to the host log. Output is sent a line at a time, so text without
+/// a trailing newline appears once the line is completed or the guest exits.
+///
+/// Unlike `std::print!`, this formats into a fixed buffer and never
+/// allocates.
+#[macro_export]
+macro_rules! print {
+    ($($arg:tt)*) => {
+        $crate::__print(::core::format_args!($($arg)*))
+    };
+}
+
+/// Prints to the host log, with a newline. See [print].
+#[macro_export]
+macro_rules! println {
+    () => {
+        $crate::print!("\n")
+    };
+    ($($arg:tt)*) => {
+        $crate::__print(::core::format_args!("{}\n", ::core::format_args!($($arg)*)))
+    };
+}
+
+/// Prints to the host log. The host cannot tell these lines apart from those
+/// of [print], but they are buffered separately so that partial lines of the
+/// two do not interleave.
+#[macro_export]
+macro_rules! eprint {
+    ($($arg:tt)*) => {
+        $crate::__eprint(::core::format_args!($($arg)*))
+    };
+}
+
+/// Prints to the host log, with a newline. See [eprint].
+#[macro_export]
+macro_rules! eprintln {
+    () => {
+        $crate::eprint!("\n")
+    };
+    ($($arg:tt)*) => {
+        $crate::__eprint(::core::format_args!("{}\n", ::core::format_args!($($arg)*)))
+    };
+}
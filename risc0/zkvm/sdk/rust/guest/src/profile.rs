@@ -0,0 +1,82 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Measures how many cycles regions of the guest take.
+//!
+//! ```ignore
+//! let _span = zkvm_guest::profile::span("verify");
+//! // Everything until `_span` is dropped is counted.
+//! ```
+//!
+//! The host sums the cycles spent inside each span by name and reports them
+//! in its run statistics.
+
+use _alloc::boxed::Box;
+use core::mem::MaybeUninit;
+
+use crate::gpio::{
+    GetCycleDescriptor, SpanDescriptor, GPIO_DESC_GET_CYCLE, GPIO_DESC_SPAN, GPIO_GET_CYCLE,
+    GPIO_SPAN,
+};
+
+/// Returns the cycle on which it is called. Cycles are counted from the start
+/// of the execution, including those spent loading the guest.
+///
+/// Each call permanently uses up one word of the heap, which is never freed.
+/// Calling this in a long loop can exhaust the heap; spans do not have this
+/// cost, so prefer [span] for code that runs many times.
+pub fn cycle_count() -> usize {
+    // The host can only fill in memory the guest has never read or written,
+    // so no word can be reused for a second count, and each count gets a
+    // fresh allocation.
+    let cycle: Box<MaybeUninit<usize>> = Box::new_uninit();
+    unsafe {
+        GPIO_DESC_GET_CYCLE.write_volatile(GetCycleDescriptor {
+            addr: cycle.as_ptr() as usize,
+        });
+        GPIO_GET_CYCLE.write_volatile(GPIO_DESC_GET_CYCLE);
+        cycle.as_ptr().read_volatile()
+    }
+}
+
+/// Enters the span `name` until the returned guard is dropped. Spans must
+/// be exited in the reverse order they were entered in, which dropping
+/// guards at the end of their scopes does.
+pub fn span(name: &str) -> Span<'_> {
+    send(name, true);
+    Span { name }
+}
+
+/// A profiling span entered with [span], exited when dropped.
+#[must_use = "the span is exited as soon as it is dropped"]
+pub struct Span<'a> {
+    name: &'a str,
+}
+
+impl Drop for Span<'_> {
+    fn drop(&mut self) {
+        send(self.name, false);
+    }
+}
+
+fn send(name: &str, enter: bool) {
+    unsafe {
+        GPIO_DESC_SPAN.write_volatile(SpanDescriptor {
+            name: name.as_ptr() as usize,
+            len: name.len(),
+            enter: enter as usize,
+        });
+        GPIO_SPAN.write_volatile(GPIO_DESC_SPAN);
+    }
+}
@@ -16,11 +16,11 @@ use _alloc::{boxed::Box, vec::Vec};
 use core::mem;
 
 use zkvm_core::Digest;
+use zkvm_platform::{memory, WORD_SIZE};
 
 use crate::{
     align_up,
     gpio::{SHADescriptor, GPIO_SHA},
-    REGION_SHA_START, WORD_SIZE,
 };
 
 pub struct SHA256 {
@@ -32,12 +32,12 @@ static mut CUR_DESC: usize = 0;
 // Compute the padded size for data of size 'len' which is equal to:
 // len + 1 (terminating byte) + sizeof(uint64_t),
 // rounded up to nearest multiple of 64.
-fn padded_size(size: usize) -> usize {
+pub(crate) fn padded_size(size: usize) -> usize {
     align_up(size + 1 + mem::size_of::<u64>(), 64)
 }
 
 fn get_cur_desc() -> *mut SHADescriptor {
-    unsafe { (REGION_SHA_START as *mut SHADescriptor).add(CUR_DESC) }
+    unsafe { (memory::SHA.start() as *mut SHADescriptor).add(CUR_DESC) }
 }
 
 impl SHA256 {
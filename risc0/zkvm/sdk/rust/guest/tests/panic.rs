@@ -0,0 +1,43 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec;
+use core::panic::PanicInfo;
+
+use zkvm_guest::env;
+
+fn hook(_: &PanicInfo) {
+    env::write(&0xdead_u32);
+}
+
+#[zkvm_guest::main]
+fn main(mode: u32) {
+    match mode {
+        0 => {
+            // More than the whole heap.
+            let buf = vec![0u8; 2 * 1024 * 1024];
+            env::write(&(buf.as_ptr() as u32));
+        }
+        1 => panic!("{:x>2000}", ""),
+        _ => {
+            zkvm_guest::set_panic_hook(hook);
+            panic!("Hooked");
+        }
+    }
+}
@@ -0,0 +1,39 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![no_main]
+#![no_std]
+
+use zkvm_guest::{env, profile};
+
+#[inline(never)]
+fn work(iterations: u32) -> u32 {
+    let _span = profile::span("work");
+    let mut acc: u32 = 0;
+    for i in 0..iterations {
+        acc = acc.wrapping_mul(31).wrapping_add(i);
+    }
+    acc
+}
+
+#[zkvm_guest::main]
+fn main(iterations: u32) -> u32 {
+    let start = profile::cycle_count();
+    let acc = {
+        let _span = profile::span("main");
+        work(iterations) ^ work(iterations)
+    };
+    env::write(&((profile::cycle_count() - start) as u32));
+    acc
+}
@@ -0,0 +1,28 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![no_main]
+#![no_std]
+
+use zkvm_guest::env;
+
+zkvm_guest::entry!(main);
+
+pub fn main() {
+    // The input is not valid UTF-8, so reading it as a string fails and
+    // leaves it to be read again as bytes.
+    let failed = env::try_read::<&str>().is_err();
+    let bytes: &[u8] = env::read();
+    env::commit(&(failed, bytes.len() as u32));
+}
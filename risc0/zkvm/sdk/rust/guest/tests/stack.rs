@@ -0,0 +1,38 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use zkvm_guest::env;
+
+zkvm_guest::entry!(main);
+
+// Each call keeps a 1KB frame alive across the recursive call below it.
+#[inline(never)]
+fn recurse(depth: u32) -> u32 {
+    let mut frame = [0u8; 1024];
+    frame[0] = depth as u8;
+    if depth > 0 {
+        recurse(depth - 1);
+    }
+    unsafe { ptr::read_volatile(&frame[0]) as u32 }
+}
+
+pub fn main() {
+    let depth: u32 = env::read();
+    env::commit(&recurse(depth));
+}
@@ -14,9 +14,56 @@
 
 use std::fmt::{self, Display};
 
+/// Classifies an [Exception] so that callers can react to specific failures
+/// without matching on the message text.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ExceptionKind {
+    /// Any failure not covered by a more specific kind.
+    Other,
+    /// The guest did not finish within the configured cycle limit.
+    CycleLimitExceeded,
+    /// The proof was abandoned before it completed, through a
+    /// [CancelHandle](crate::CancelHandle).
+    Cancelled,
+    /// The guest failed, either by panicking or by returning an error from
+    /// an entry point declared with `#[zkvm_guest::main]`. The message is
+    /// the one reported by the guest.
+    GuestFault,
+    /// The journal of a receipt does not match the commitment in its seal.
+    JournalMismatch,
+}
+
+/// The details of a failure reported by the guest, see
+/// [ExceptionKind::GuestFault].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuestPanic {
+    /// The message reported by the guest.
+    pub message: String,
+    /// Where in the guest source the failure happened. Absent for errors
+    /// returned from entry points, and for C++ guests.
+    pub location: Option<GuestLocation>,
+}
+
+/// A source location in the guest.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuestLocation {
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
 #[derive(Debug)]
 pub struct Exception {
+    pub(crate) kind: ExceptionKind,
     pub(crate) what: String,
+    pub(crate) guest_panic: Option<GuestPanic>,
+}
+
+impl Display for GuestLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
 }
 
 impl Display for Exception {
@@ -29,12 +76,28 @@ impl std::error::Error for Exception {}
 
 impl Exception {
     pub fn new(what: &str) -> Self {
+        Exception::with_kind(ExceptionKind::Other, what.to_string())
+    }
+
+    pub fn with_kind(kind: ExceptionKind, what: String) -> Self {
         Exception {
-            what: what.to_string(),
+            kind,
+            what,
+            guest_panic: None,
         }
     }
 
+    pub fn kind(&self) -> ExceptionKind {
+        self.kind
+    }
+
     pub fn what(&self) -> &str {
         &self.what
     }
+
+    /// The details reported by the guest, if this is an
+    /// [ExceptionKind::GuestFault].
+    pub fn guest_panic(&self) -> Option<&GuestPanic> {
+        self.guest_panic.as_ref()
+    }
 }
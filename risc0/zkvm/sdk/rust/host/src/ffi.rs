@@ -12,27 +12,50 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{ffi::CStr, os::raw::c_char};
+use std::{
+    ffi::CStr,
+    os::raw::{c_char, c_void},
+};
 
-use crate::exception::Exception;
+use crate::exception::{Exception, ExceptionKind, GuestLocation, GuestPanic};
 
 pub(crate) enum RawString {}
 pub(crate) enum RawProver {}
 pub(crate) enum RawReceipt {}
 
+const ERROR_CYCLE_LIMIT: u32 = 1;
+const ERROR_CANCELLED: u32 = 2;
+const ERROR_GUEST_FAULT: u32 = 3;
+
 #[repr(C)]
 pub(crate) struct RawError {
     msg: *const RawString,
+    code: u32,
+    fault_msg: *const RawString,
+    fault_file: *const RawString,
+    fault_line: u32,
+    fault_column: u32,
 }
 
 impl Default for RawError {
     fn default() -> Self {
         Self {
             msg: std::ptr::null(),
+            code: 0,
+            fault_msg: std::ptr::null(),
+            fault_file: std::ptr::null(),
+            fault_line: 0,
+            fault_column: 0,
         }
     }
 }
 
+pub(crate) type RawLogCallback = extern "C" fn(ctx: *mut c_void, cycle: u32, msg: *const c_char);
+
+pub(crate) type RawIoCallback = extern "C" fn(ctx: *mut c_void, buf: *const u8, len: usize);
+
+pub(crate) type RawCancelCallback = extern "C" fn(ctx: *mut c_void) -> u32;
+
 #[inline]
 pub(crate) fn check<T, F>(err: RawError, ok: F) -> crate::Result<T>
 where
@@ -41,27 +64,110 @@ where
     if err.msg.is_null() {
         Ok(ok())
     } else {
-        let what = unsafe {
-            let str = risc0_string_ptr(err.msg);
-            let msg = CStr::from_ptr(str).to_str().unwrap().to_string();
-            risc0_string_free(err.msg);
-            msg
+        let what = unsafe { take_string(err.msg) };
+        let kind = match err.code {
+            ERROR_CYCLE_LIMIT => ExceptionKind::CycleLimitExceeded,
+            ERROR_CANCELLED => ExceptionKind::Cancelled,
+            ERROR_GUEST_FAULT => ExceptionKind::GuestFault,
+            _ => ExceptionKind::Other,
         };
-        Err(Exception { what })
+        let mut exception = Exception::with_kind(kind, what);
+        if !err.fault_msg.is_null() {
+            let location = if err.fault_file.is_null() {
+                None
+            } else {
+                Some(GuestLocation {
+                    file: unsafe { take_string(err.fault_file) },
+                    line: err.fault_line,
+                    column: err.fault_column,
+                })
+            };
+            exception.guest_panic = Some(GuestPanic {
+                message: unsafe { take_string(err.fault_msg) },
+                location,
+            });
+        }
+        Err(exception)
     }
 }
 
+/// Copies out and frees a string returned by the C++ side. Guest messages are
+/// not necessarily UTF-8, so invalid sequences are replaced.
+unsafe fn take_string(str: *const RawString) -> String {
+    let msg = CStr::from_ptr(risc0_string_ptr(str))
+        .to_string_lossy()
+        .into_owned();
+    risc0_string_free(str);
+    msg
+}
+
 extern "C" {
     pub(crate) fn risc0_init();
 
+    pub(crate) fn risc0_set_log_level(level: u32);
+
     pub(crate) fn risc0_string_ptr(str: *const RawString) -> *const c_char;
 
     pub(crate) fn risc0_string_free(str: *const RawString);
 
     pub(crate) fn risc0_prover_new(err: *mut RawError, elf_path: *const i8) -> *mut RawProver;
 
+    pub(crate) fn risc0_prover_new_from_elf(
+        err: *mut RawError,
+        elf: *const u8,
+        len: usize,
+    ) -> *mut RawProver;
+
     pub(crate) fn risc0_prover_free(err: *mut RawError, prover: *mut RawProver);
 
+    pub(crate) fn risc0_prover_set_log_callback(
+        err: *mut RawError,
+        prover: *mut RawProver,
+        callback: RawLogCallback,
+        ctx: *mut c_void,
+    );
+
+    pub(crate) fn risc0_prover_set_io_callbacks(
+        err: *mut RawError,
+        prover: *mut RawProver,
+        on_write: RawIoCallback,
+        on_commit: RawIoCallback,
+        ctx: *mut c_void,
+    );
+
+    pub(crate) fn risc0_prover_set_cancel_callback(
+        err: *mut RawError,
+        prover: *mut RawProver,
+        callback: RawCancelCallback,
+        ctx: *mut c_void,
+    );
+
+    pub(crate) fn risc0_prover_set_guest_log_level(
+        err: *mut RawError,
+        prover: *mut RawProver,
+        level: u32,
+    );
+
+    pub(crate) fn risc0_prover_set_max_cycles(
+        err: *mut RawError,
+        prover: *mut RawProver,
+        max_cycles: usize,
+    );
+
+    pub(crate) fn risc0_prover_set_sample_interval(
+        err: *mut RawError,
+        prover: *mut RawProver,
+        cycles: u32,
+    );
+
+    pub(crate) fn risc0_prover_set_key(
+        err: *mut RawError,
+        prover: *mut RawProver,
+        name: *const c_char,
+        key: *const u32,
+        len: usize,
+    );
+
     pub(crate) fn risc0_prover_add_input(
         err: *mut RawError,
         prover: *mut RawProver,
@@ -79,12 +185,43 @@ extern "C" {
     pub(crate) fn risc0_prover_run(err: *mut RawError, prover: *mut RawProver)
         -> *const RawReceipt;
 
+    pub(crate) fn risc0_prover_write_profile(
+        err: *mut RawError,
+        prover: *mut RawProver,
+        path: *const c_char,
+    );
+
+    pub(crate) fn risc0_prover_get_span_count(
+        err: *mut RawError,
+        prover: *const RawProver,
+    ) -> usize;
+
+    pub(crate) fn risc0_prover_get_span(
+        err: *mut RawError,
+        prover: *const RawProver,
+        idx: usize,
+        count: *mut u64,
+        cycles: *mut u64,
+    ) -> *const c_char;
+
+    pub(crate) fn risc0_prover_get_max_stack_usage(
+        err: *mut RawError,
+        prover: *const RawProver,
+    ) -> u32;
+
     pub(crate) fn risc0_receipt_verify(
         err: *mut RawError,
         elf_path: *const i8,
         receipt: *const RawReceipt,
     );
 
+    pub(crate) fn risc0_receipt_verify_elf(
+        err: *mut RawError,
+        elf: *const u8,
+        len: usize,
+        receipt: *const RawReceipt,
+    );
+
     pub(crate) fn risc0_receipt_get_seal_buf(
         err: *mut RawError,
         receipt: *const RawReceipt,
@@ -0,0 +1,156 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use zkvm_core::{Digest, DIGEST_WORDS};
+
+use crate::{Exception, ExceptionKind, Result};
+
+/// Journals up to this many bytes are stored in the seal as-is rather than
+/// hashed.
+pub const INLINE_JOURNAL_BYTES: usize = DIGEST_WORDS * 4;
+
+/// What a seal commits to about its journal, as written by the guest to its
+/// output registers when it finishes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JournalCommitment {
+    /// The journal itself, for journals of at most [INLINE_JOURNAL_BYTES].
+    /// Only the first `len` bytes of `words` are meaningful.
+    Inline {
+        words: [u32; DIGEST_WORDS],
+        len: usize,
+    },
+    /// The SHA-256 digest of a longer journal.
+    Digest { digest: Digest, len: usize },
+}
+
+impl JournalCommitment {
+    /// Decodes the commitment from the start of a seal.
+    pub fn from_seal(seal: &[u32]) -> Result<Self> {
+        if seal.len() <= DIGEST_WORDS {
+            return Err(Exception::new(
+                "Seal is too short to contain a journal commitment",
+            ));
+        }
+        let mut words = [0; DIGEST_WORDS];
+        words.copy_from_slice(&seal[..DIGEST_WORDS]);
+        let len = seal[DIGEST_WORDS] as usize;
+        Ok(if len <= INLINE_JOURNAL_BYTES {
+            JournalCommitment::Inline { words, len }
+        } else {
+            JournalCommitment::Digest {
+                digest: Digest::new(words),
+                len,
+            }
+        })
+    }
+
+    /// Computes the commitment a guest would make to `journal`.
+    pub fn from_journal(journal: &[u8]) -> Self {
+        let len = journal.len();
+        if len <= INLINE_JOURNAL_BYTES {
+            let mut words = [0; DIGEST_WORDS];
+            for (word, chunk) in words.iter_mut().zip(journal.chunks(4)) {
+                let mut bytes = [0; 4];
+                bytes[..chunk.len()].copy_from_slice(chunk);
+                *word = u32::from_le_bytes(bytes);
+            }
+            JournalCommitment::Inline { words, len }
+        } else {
+            JournalCommitment::Digest {
+                digest: zkvm_verify::hash_bytes(journal),
+                len,
+            }
+        }
+    }
+
+    /// The length of the journal in bytes.
+    pub fn len(&self) -> usize {
+        match self {
+            JournalCommitment::Inline { len, .. } | JournalCommitment::Digest { len, .. } => *len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks whether `journal` is the one committed to. Inline commitments
+    /// are compared on their first `len` bytes only, since the guest need not
+    /// clear the rest of the final word.
+    pub fn matches(&self, journal: &[u8]) -> bool {
+        match (self, JournalCommitment::from_journal(journal)) {
+            (
+                JournalCommitment::Inline { words, len },
+                JournalCommitment::Inline {
+                    words: other,
+                    len: other_len,
+                },
+            ) => {
+                let bytes = words.iter().flat_map(|word| word.to_le_bytes());
+                let other = other.iter().flat_map(|word| word.to_le_bytes());
+                *len == other_len && bytes.zip(other).take(*len).all(|(a, b)| a == b)
+            }
+            (this, other) => *this == other,
+        }
+    }
+
+    pub(crate) fn check(&self, journal: &[u8]) -> Result<()> {
+        match self.matches(journal) {
+            true => Ok(()),
+            false => Err(Exception::with_kind(
+                ExceptionKind::JournalMismatch,
+                "Receipt journal does not match the seal".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::JournalCommitment;
+
+    #[test]
+    fn inline() {
+        let mut seal = vec![0x64636261, 0x65, 0, 0, 0, 0, 0, 0, 5];
+        let commitment = JournalCommitment::from_seal(&seal).unwrap();
+        assert_eq!(commitment, JournalCommitment::from_journal(b"abcde"));
+        assert!(commitment.matches(b"abcde"));
+        assert!(!commitment.matches(b"abcdf"));
+        assert!(!commitment.matches(b"abcd"));
+
+        // Bytes past the end of the journal are not committed to.
+        seal[1] |= 0xff00;
+        let commitment = JournalCommitment::from_seal(&seal).unwrap();
+        assert!(commitment.matches(b"abcde"));
+    }
+
+    #[test]
+    fn digest() {
+        let journal = [7u8; 33];
+        let commitment = JournalCommitment::from_journal(&journal);
+        let words = match commitment {
+            JournalCommitment::Digest { digest, len } => {
+                assert_eq!(len, 33);
+                digest.as_slice().to_vec()
+            }
+            _ => panic!("expected a digest"),
+        };
+        let mut seal = words;
+        seal.push(33);
+        assert_eq!(JournalCommitment::from_seal(&seal).unwrap(), commitment);
+        assert!(commitment.matches(&journal));
+        assert!(!commitment.matches(&journal[1..]));
+        assert!(JournalCommitment::from_seal(&seal[..8]).is_err());
+    }
+}
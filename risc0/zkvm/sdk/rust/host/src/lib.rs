@@ -13,21 +13,173 @@
 // limitations under the License.
 
 use log::LevelFilter;
-use std::{ffi::CString, mem};
+use std::{
+    any::Any,
+    cell::RefCell,
+    ffi::{CStr, CString},
+    mem,
+    os::raw::{c_char, c_void},
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    slice,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
 mod exception;
 mod ffi;
+mod journal;
+mod method;
+mod opts;
+mod stats;
 
-pub use exception::Exception;
+pub use exception::{Exception, ExceptionKind, GuestLocation, GuestPanic};
+pub use journal::{JournalCommitment, INLINE_JOURNAL_BYTES};
+pub use method::{Method, TypedReceipt};
+pub use opts::{IoHandler, ProverOpts};
+pub use stats::{RunStats, SpanStats};
 
 pub type Result<T> = std::result::Result<T, Exception>;
 
+/// The `log` target used when forwarding guest log lines.
+pub const GUEST_LOG_TARGET: &str = "zkvm::guest";
+
+/// Receives each log line emitted by the guest along with the cycle on which
+/// it was emitted.
+pub type LogSink = Box<dyn FnMut(u32, &str) + Send>;
+
 pub struct Receipt {
     ptr: *const ffi::RawReceipt,
 }
 
+// The C++ receipt is never mutated after construction, so it may be shared
+// between threads.
+unsafe impl Send for Receipt {}
+unsafe impl Sync for Receipt {}
+
 pub struct Prover {
     ptr: *mut ffi::RawProver,
+    // Boxed so that the address handed to the C++ side stays stable when the
+    // sink is replaced.
+    callbacks: Box<Callbacks>,
+}
+
+// What the C++ prover calls back into on the thread running the proof.
+struct Callbacks {
+    log_sink: LogSink,
+    io_handler: Option<Box<dyn IoHandler>>,
+    // Set through a `CancelHandle`, possibly from another thread.
+    cancelled: Arc<AtomicBool>,
+    // The message of the first panic raised by a callback, which
+    // `Prover::run` reports. Kept apart from `cancelled` so that it only
+    // stops the run it happened in.
+    panic: RefCell<Option<String>>,
+}
+
+// The C++ prover is exclusively owned and has no thread affinity, and every
+// callback it holds is `Send`. It is not `Sync`: `run` mutates the C++ object
+// through a shared reference.
+unsafe impl Send for Prover {}
+
+/// Sets the verbosity of diagnostics emitted by the C++ prover itself, which
+/// are written to stderr. Levels up to and including `Info` disable them.
+///
+/// This does not affect guest logs, which are always forwarded to the
+/// [LogSink] of the running [Prover].
+pub fn set_log_level(level: LevelFilter) {
+    let level = match level {
+        LevelFilter::Off | LevelFilter::Error | LevelFilter::Warn | LevelFilter::Info => 0,
+        LevelFilter::Debug => 1,
+        LevelFilter::Trace => 2,
+    };
+    unsafe { ffi::risc0_set_log_level(level) };
+}
+
+fn default_log_sink() -> LogSink {
+    Box::new(|cycle, msg| log::info!(target: GUEST_LOG_TARGET, "[C{}] {}", cycle, msg))
+}
+
+impl Callbacks {
+    /// Runs `f` on the callbacks behind `ctx` without letting a panic unwind
+    /// into C++. A panic is recorded instead, which stops the proof soon;
+    /// callbacks are not called again after that.
+    fn call<F: FnOnce(&mut Callbacks)>(ctx: *mut c_void, f: F) {
+        let callbacks = unsafe { &mut *(ctx as *mut Callbacks) };
+        if callbacks.panic.borrow().is_some() {
+            return;
+        }
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| f(&mut *callbacks))) {
+            *callbacks.panic.borrow_mut() = Some(panic_message(payload.as_ref()));
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    }
+}
+
+extern "C" fn log_callback(ctx: *mut c_void, cycle: u32, msg: *const c_char) {
+    Callbacks::call(ctx, |callbacks| {
+        let msg = unsafe { CStr::from_ptr(msg) }.to_string_lossy();
+        (callbacks.log_sink)(cycle, &msg);
+    });
+}
+
+extern "C" fn cancel_callback(ctx: *mut c_void) -> u32 {
+    let callbacks = unsafe { &*(ctx as *const Callbacks) };
+    (callbacks.cancelled.load(Ordering::Relaxed) || callbacks.panic.borrow().is_some()) as u32
+}
+
+extern "C" fn write_callback(ctx: *mut c_void, buf: *const u8, len: usize) {
+    Callbacks::call(ctx, |callbacks| {
+        if let Some(handler) = callbacks.io_handler.as_mut() {
+            handler.on_write(unsafe { raw_slice(buf, len) });
+        }
+    });
+}
+
+extern "C" fn commit_callback(ctx: *mut c_void, buf: *const u8, len: usize) {
+    Callbacks::call(ctx, |callbacks| {
+        if let Some(handler) = callbacks.io_handler.as_mut() {
+            handler.on_commit(unsafe { raw_slice(buf, len) });
+        }
+    });
+}
+
+fn c_string(str: &str) -> Result<CString> {
+    CString::new(str).map_err(|_| Exception::new("String contains an interior NUL byte"))
+}
+
+fn path_to_c_string(path: &Path) -> Result<CString> {
+    #[cfg(unix)]
+    let bytes = {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_os_str().as_bytes()
+    };
+    #[cfg(not(unix))]
+    let bytes = path
+        .to_str()
+        .ok_or_else(|| Exception::new("Path is not valid UTF-8"))?
+        .as_bytes();
+    CString::new(bytes).map_err(|_| Exception::new("Path contains an interior NUL byte"))
+}
+
+/// Borrows a buffer handed out by the C++ side, which may pass a null pointer
+/// for an empty buffer.
+unsafe fn raw_slice<'a, T>(buf: *const T, len: usize) -> &'a [T] {
+    if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(buf, len)
+    }
 }
 
 fn into_words(slice: &[u8]) -> Result<Vec<u32>> {
@@ -45,13 +197,38 @@ fn into_words(slice: &[u8]) -> Result<Vec<u32>> {
 }
 
 impl Receipt {
-    pub fn verify(&self, elf_path: &str) -> Result<()> {
+    /// Verifies the receipt against the method ID stored next to the ELF at
+    /// `elf_path`, in `<elf_path>.id`.
+    pub fn verify<P: AsRef<Path>>(&self, elf_path: P) -> Result<()> {
         let mut err = ffi::RawError::default();
-        let str = CString::new(elf_path).unwrap();
+        let str = path_to_c_string(elf_path.as_ref())?;
         unsafe { ffi::risc0_receipt_verify(&mut err, str.as_ptr(), self.ptr) };
         ffi::check(err, || ())
     }
 
+    /// Verifies the receipt against an ELF image that is already in memory.
+    /// The method ID is computed from the image, which is considerably
+    /// slower than reading a precomputed `.id` file.
+    pub fn verify_elf(&self, elf: &[u8]) -> Result<()> {
+        let mut err = ffi::RawError::default();
+        unsafe { ffi::risc0_receipt_verify_elf(&mut err, elf.as_ptr(), elf.len(), self.ptr) };
+        ffi::check(err, || ())
+    }
+
+    /// Verifies the receipt against the bytes of a method ID, such as the
+    /// `*_ID` constants that `zkvm_build` generates.
+    pub fn verify_method_id(&self, method_id: &[u8]) -> Result<()> {
+        let method_id = zkvm_verify::MethodId::from_bytes(method_id)
+            .map_err(|err| Exception::new(&format!("Invalid method ID: {}", err)))?;
+        zkvm_verify::verify(&method_id, self.get_seal()?, self.get_journal()?).map_err(|err| {
+            let kind = match err {
+                zkvm_verify::Error::JournalMismatch => ExceptionKind::JournalMismatch,
+                _ => ExceptionKind::Other,
+            };
+            Exception::with_kind(kind, format!("Receipt verification failed: {}", err))
+        })
+    }
+
     pub fn get_seal(&self) -> Result<&[u32]> {
         unsafe {
             let mut err = ffi::RawError::default();
@@ -60,7 +237,7 @@ impl Receipt {
             let mut err = ffi::RawError::default();
             let len = ffi::risc0_receipt_get_seal_len(&mut err, self.ptr);
             let len = ffi::check(err, || len)?;
-            Ok(std::slice::from_raw_parts(buf, len))
+            Ok(raw_slice(buf, len))
         }
     }
 
@@ -72,21 +249,144 @@ impl Receipt {
             let mut err = ffi::RawError::default();
             let len = ffi::risc0_receipt_get_journal_len(&mut err, self.ptr);
             let len = ffi::check(err, || len)?;
-            Ok(std::slice::from_raw_parts(buf, len))
+            Ok(raw_slice(buf, len))
         }
     }
 
     pub fn get_journal_vec(&self) -> Result<Vec<u32>> {
         into_words(self.get_journal()?)
     }
+
+    /// Decodes what the seal commits to about the journal: either the
+    /// journal itself, if it is short, or its digest.
+    pub fn journal_commitment(&self) -> Result<JournalCommitment> {
+        JournalCommitment::from_seal(self.get_seal()?)
+    }
+
+    /// Checks that the journal matches the commitment in the seal. This does
+    /// not check the seal itself; [Receipt::verify] does both.
+    pub fn check_journal(&self) -> Result<()> {
+        self.journal_commitment()?.check(self.get_journal()?)
+    }
 }
 
 impl Prover {
-    pub fn new(elf_path: &str) -> Result<Self> {
+    pub fn new<P: AsRef<Path>>(elf_path: P) -> Result<Self> {
+        Prover::new_with_opts(elf_path, ProverOpts::default())
+    }
+
+    pub fn new_with_opts<P: AsRef<Path>>(elf_path: P, opts: ProverOpts) -> Result<Self> {
+        let str = path_to_c_string(elf_path.as_ref())?;
+        Prover::create(opts, |err| unsafe {
+            ffi::risc0_prover_new(err, str.as_ptr())
+        })
+    }
+
+    /// Creates a prover for a guest whose ELF image is already in memory,
+    /// e.g. embedded with `include_bytes!`.
+    pub fn from_elf(elf: &[u8]) -> Result<Self> {
+        Prover::from_elf_with_opts(elf, ProverOpts::default())
+    }
+
+    pub fn from_elf_with_opts(elf: &[u8], opts: ProverOpts) -> Result<Self> {
+        Prover::create(opts, |err| unsafe {
+            ffi::risc0_prover_new_from_elf(err, elf.as_ptr(), elf.len())
+        })
+    }
+
+    fn create<F>(opts: ProverOpts, new: F) -> Result<Self>
+    where
+        F: FnOnce(&mut ffi::RawError) -> *mut ffi::RawProver,
+    {
+        if let Some(level) = opts.log_level {
+            set_log_level(level);
+        }
+        let mut err = ffi::RawError::default();
+        let ptr = new(&mut err);
+        let mut prover = ffi::check(err, || Prover {
+            ptr,
+            callbacks: Box::new(Callbacks {
+                log_sink: opts.log_sink.unwrap_or_else(default_log_sink),
+                io_handler: opts.io_handler,
+                cancelled: Arc::new(AtomicBool::new(false)),
+                panic: RefCell::new(None),
+            }),
+        })?;
+        let callbacks = prover.callbacks.as_mut() as *mut Callbacks as *mut c_void;
+
+        let mut err = ffi::RawError::default();
+        unsafe {
+            ffi::risc0_prover_set_cancel_callback(&mut err, prover.ptr, cancel_callback, callbacks)
+        };
+        ffi::check(err, || ())?;
+
         let mut err = ffi::RawError::default();
-        let str = CString::new(elf_path).unwrap();
-        let ptr = unsafe { ffi::risc0_prover_new(&mut err, str.as_ptr()) };
-        ffi::check(err, || Prover { ptr })
+        unsafe {
+            ffi::risc0_prover_set_log_callback(&mut err, prover.ptr, log_callback, callbacks)
+        };
+        ffi::check(err, || ())?;
+
+        if prover.callbacks.io_handler.is_some() {
+            let mut err = ffi::RawError::default();
+            unsafe {
+                ffi::risc0_prover_set_io_callbacks(
+                    &mut err,
+                    prover.ptr,
+                    write_callback,
+                    commit_callback,
+                    callbacks,
+                )
+            };
+            ffi::check(err, || ())?;
+        }
+
+        if let Some(level) = opts.guest_log_level {
+            let mut err = ffi::RawError::default();
+            unsafe { ffi::risc0_prover_set_guest_log_level(&mut err, prover.ptr, level as u32) };
+            ffi::check(err, || ())?;
+        }
+
+        if let Some(max_cycles) = opts.max_cycles {
+            let mut err = ffi::RawError::default();
+            unsafe { ffi::risc0_prover_set_max_cycles(&mut err, prover.ptr, max_cycles) };
+            ffi::check(err, || ())?;
+        }
+
+        if let Some(cycles) = opts.sample_interval {
+            let mut err = ffi::RawError::default();
+            unsafe { ffi::risc0_prover_set_sample_interval(&mut err, prover.ptr, cycles) };
+            ffi::check(err, || ())?;
+        }
+
+        for (name, key) in opts.keys.iter() {
+            let name = c_string(name)?;
+            let mut err = ffi::RawError::default();
+            unsafe {
+                ffi::risc0_prover_set_key(
+                    &mut err,
+                    prover.ptr,
+                    name.as_ptr(),
+                    key.as_ptr(),
+                    key.len(),
+                )
+            };
+            ffi::check(err, || ())?;
+        }
+
+        if !opts.input.is_empty() {
+            prover.add_input(&opts.input)?;
+        }
+
+        Ok(prover)
+    }
+
+    /// Replaces the sink that receives guest log lines. By default they are
+    /// forwarded to the `log` crate at `Info` level under [GUEST_LOG_TARGET].
+    pub fn set_log_sink<F>(&mut self, sink: F)
+    where
+        F: FnMut(u32, &str) + Send + 'static,
+    {
+        self.callbacks.log_sink = Box::new(sink);
     }
 
     pub fn add_input(&mut self, slice: &[u32]) -> Result<()> {
@@ -110,7 +410,7 @@ impl Prover {
             let mut err = ffi::RawError::default();
             let len = ffi::risc0_prover_get_output_len(&mut err, self.ptr);
             let len = ffi::check(err, || len)?;
-            Ok(std::slice::from_raw_parts(buf, len))
+            Ok(raw_slice(buf, len))
         }
     }
 
@@ -118,10 +418,76 @@ impl Prover {
         into_words(self.get_output()?)
     }
 
+    /// Runs the guest and proves it. A panic in the log sink or the
+    /// [IoHandler] stops the proof and is reported here as an error. Unlike
+    /// a [CancelHandle], it does not carry over, so the prover can run again.
     pub fn run(&self) -> Result<Receipt> {
         let mut err = ffi::RawError::default();
         let ptr = unsafe { ffi::risc0_prover_run(&mut err, self.ptr) };
-        ffi::check(err, || Receipt { ptr })
+        let receipt = ffi::check(err, || Receipt { ptr });
+        match self.callbacks.panic.borrow_mut().take() {
+            Some(msg) => Err(Exception::new(&format!("Host callback panicked: {}", msg))),
+            None => receipt,
+        }
+    }
+
+    /// Writes the call stacks sampled by the last [run](Prover::run) to `path`
+    /// as folded stacks, which flame graph tools such as `inferno` render.
+    /// Requires [ProverOpts::with_sample_interval].
+    pub fn write_profile<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path_to_c_string(path.as_ref())?;
+        let mut err = ffi::RawError::default();
+        unsafe { ffi::risc0_prover_write_profile(&mut err, self.ptr, path.as_ptr()) };
+        ffi::check(err, || ())
+    }
+
+    /// Returns the statistics collected by the last [run](Prover::run), even
+    /// if it failed.
+    pub fn stats(&self) -> Result<RunStats> {
+        let mut stats = RunStats::default();
+        let mut err = ffi::RawError::default();
+        let len = unsafe { ffi::risc0_prover_get_span_count(&mut err, self.ptr) };
+        let len = ffi::check(err, || len)?;
+        for idx in 0..len {
+            let mut span = SpanStats::default();
+            let mut err = ffi::RawError::default();
+            let name = unsafe {
+                ffi::risc0_prover_get_span(
+                    &mut err,
+                    self.ptr,
+                    idx,
+                    &mut span.count,
+                    &mut span.cycles,
+                )
+            };
+            let name = ffi::check(err, || name)?;
+            let name = unsafe { CStr::from_ptr(name) }
+                .to_string_lossy()
+                .into_owned();
+            stats.spans.insert(name, span);
+        }
+        let mut err = ffi::RawError::default();
+        let max_stack_usage = unsafe { ffi::risc0_prover_get_max_stack_usage(&mut err, self.ptr) };
+        stats.max_stack_usage = ffi::check(err, || max_stack_usage)?;
+        Ok(stats)
+    }
+
+    /// Returns a handle that cancels the proofs of this prover, from any
+    /// thread.
+    pub fn cancel_handle(&self) -> CancelHandle {
+        CancelHandle(self.callbacks.cancelled.clone())
+    }
+}
+
+/// Cancels the proofs of a [Prover], see [Prover::cancel_handle].
+#[derive(Clone)]
+pub struct CancelHandle(Arc<AtomicBool>);
+
+impl CancelHandle {
+    /// Makes the running proof, and any later one, fail with
+    /// [ExceptionKind::Cancelled] shortly afterwards.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
     }
 }
 
@@ -143,6 +509,30 @@ impl Drop for Prover {
 
 #[ctor::ctor]
 fn init() {
-    env_logger::builder().filter_level(LevelFilter::Info).init();
     unsafe { ffi::risc0_init() };
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn callback_panic() {
+        let mut callbacks = Callbacks {
+            log_sink: Box::new(|_, msg| panic!("Bad line: {}", msg)),
+            io_handler: None,
+            cancelled: Arc::new(AtomicBool::new(false)),
+            panic: RefCell::new(None),
+        };
+        let ctx = &mut callbacks as *mut Callbacks as *mut c_void;
+        assert_eq!(cancel_callback(ctx), 0);
+        for (cycle, line) in ["first", "second"].into_iter().enumerate() {
+            log_callback(ctx, cycle as u32, CString::new(line).unwrap().as_ptr());
+        }
+        // The proof stops, but the prover is not cancelled for good.
+        assert_eq!(cancel_callback(ctx), 1);
+        assert!(!callbacks.cancelled.load(Ordering::Relaxed));
+        assert_eq!(callbacks.panic.take().as_deref(), Some("Bad line: first"));
+        assert_eq!(cancel_callback(ctx), 0);
+    }
+}
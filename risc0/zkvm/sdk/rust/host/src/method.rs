@@ -0,0 +1,173 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::marker::PhantomData;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{Exception, Prover, ProverOpts, Receipt, Result};
+
+/// A guest method, typed by the input it reads, the output it writes and the
+/// journal it commits.
+///
+/// Methods are usually declared with [methods!](crate::methods) next to the
+/// guest that implements them.
+pub struct Method<In, Out, Journal> {
+    elf_path: &'static str,
+    method_id: &'static [u8],
+    types: PhantomData<(In, Out, Journal)>,
+}
+
+/// A [Receipt] bound to the [Method] that produced it.
+pub struct TypedReceipt<Journal> {
+    receipt: Receipt,
+    method_id: &'static [u8],
+    journal: PhantomData<Journal>,
+}
+
+impl<In, Out, Journal> Method<In, Out, Journal> {
+    /// Declares the method whose ELF is at `elf_path` and whose method ID is
+    /// `method_id`, such as the `*_PATH` and `*_ID` constants that
+    /// `zkvm_build` generates. Receipts are verified against `method_id`,
+    /// whatever is at `elf_path` by then.
+    pub const fn new(elf_path: &'static str, method_id: &'static [u8]) -> Self {
+        Method {
+            elf_path,
+            method_id,
+            types: PhantomData,
+        }
+    }
+
+    pub fn elf_path(&self) -> &'static str {
+        self.elf_path
+    }
+
+    pub fn method_id(&self) -> &'static [u8] {
+        self.method_id
+    }
+
+    /// Binds a receipt received from elsewhere to this method, so that it
+    /// can be verified against it.
+    pub fn receipt(&self, receipt: Receipt) -> TypedReceipt<Journal> {
+        TypedReceipt {
+            receipt,
+            method_id: self.method_id,
+            journal: PhantomData,
+        }
+    }
+}
+
+impl<In, Out, Journal> Method<In, Out, Journal>
+where
+    In: Serialize,
+    Out: DeserializeOwned,
+{
+    /// Runs the method on `input` and proves it.
+    pub fn prove(&self, input: &In) -> Result<TypedReceipt<Journal>> {
+        let (receipt, _) = self.prove_with_opts(input, ProverOpts::default())?;
+        Ok(receipt)
+    }
+
+    /// Runs the method on `input` with the given options and proves it,
+    /// returning the output the guest wrote alongside the receipt. The
+    /// input follows any input already in `opts`.
+    pub fn prove_with_opts(
+        &self,
+        input: &In,
+        opts: ProverOpts,
+    ) -> Result<(TypedReceipt<Journal>, Out)> {
+        let input = zkvm_serde::to_vec(input)
+            .map_err(|err| Exception::new(&format!("Failed to encode input: {}", err)))?;
+        let prover = Prover::new_with_opts(self.elf_path, opts.with_input(&input))?;
+        let receipt = prover.run()?;
+        let output = decode(&prover.get_output_vec()?, "output")?;
+        Ok((self.receipt(receipt), output))
+    }
+}
+
+impl<In, Out, Journal> Clone for Method<In, Out, Journal> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<In, Out, Journal> Copy for Method<In, Out, Journal> {}
+
+impl<Journal: DeserializeOwned> TypedReceipt<Journal> {
+    /// Verifies the receipt against the method ID of its method and returns
+    /// the decoded journal.
+    pub fn verify(&self) -> Result<Journal> {
+        self.receipt.verify_method_id(self.method_id)?;
+        decode(&self.receipt.get_journal_vec()?, "journal")
+    }
+}
+
+impl<Journal> TypedReceipt<Journal> {
+    pub fn receipt(&self) -> &Receipt {
+        &self.receipt
+    }
+
+    pub fn into_receipt(self) -> Receipt {
+        self.receipt
+    }
+}
+
+fn decode<T: DeserializeOwned>(words: &[u32], what: &str) -> Result<T> {
+    zkvm_serde::from_slice(words)
+        .map_err(|err| Exception::new(&format!("Failed to decode {}: {}", what, err)))
+}
+
+/// Declares typed handles for guest methods, each as a `const` [Method].
+///
+/// ```ignore
+/// zkvm_host::methods! {
+///     /// Proves a round of the game.
+///     pub TURN(RoundParams) -> (RoundResult, RoundCommit) = (TURN_PATH, TURN_ID);
+/// }
+///
+/// let (receipt, result) = TURN.prove_with_opts(&params, ProverOpts::default())?;
+/// let commit: RoundCommit = receipt.verify()?;
+/// ```
+#[macro_export]
+macro_rules! methods {
+    ($(
+        $(#[$attr:meta])*
+        $vis:vis $name:ident($in:ty) -> ($out:ty, $journal:ty) =
+            ($elf_path:expr, $method_id:expr $(,)?);
+    )*) => {
+        $(
+            $(#[$attr])*
+            $vis const $name: $crate::Method<$in, $out, $journal> =
+                $crate::Method::new($elf_path, $method_id);
+        )*
+    };
+}
+
+#[cfg(test)]
+mod test {
+    crate::methods! {
+        SQUARE(u32) -> ((), u32) = ("path/to/square", &[1, 2, 3]);
+        pub(crate) ECHO(Vec<u32>) -> (Vec<u32>, ()) = (concat!("path/to/", "echo"), ECHO_ID);
+    }
+
+    const ECHO_ID: &[u8] = &[4, 5];
+
+    #[test]
+    fn declare() {
+        assert_eq!(SQUARE.elf_path(), "path/to/square");
+        assert_eq!(SQUARE.method_id(), &[1, 2, 3]);
+        assert_eq!(ECHO.elf_path(), "path/to/echo");
+        assert_eq!(ECHO.method_id(), ECHO_ID);
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use log::LevelFilter;
+
+use crate::LogSink;
+
+/// Receives data sent by the guest while it runs.
+pub trait IoHandler: Send {
+    /// Called with each buffer the guest sends with `env::write` or
+    /// `env::commit`, as it is sent.
+    fn on_write(&mut self, _buf: &[u8]) {}
+
+    /// Called with the journal, in order. While the journal fits in the
+    /// commit region this happens once, when the guest exits. A longer
+    /// journal arrives in parts: the region once it fills up, then each
+    /// later `env::commit`. Every commit also reaches [IoHandler::on_write].
+    fn on_commit(&mut self, _buf: &[u8]) {}
+}
+
+/// Options applied to a [Prover](crate::Prover) when it is constructed.
+///
+/// ```ignore
+/// let opts = ProverOpts::default()
+///     .with_max_cycles(1 << 18)
+///     .with_input(&to_vec(&params).unwrap());
+/// let prover = Prover::new_with_opts("path/to/method", opts)?;
+/// ```
+#[derive(Default)]
+pub struct ProverOpts {
+    pub(crate) max_cycles: Option<usize>,
+    pub(crate) sample_interval: Option<u32>,
+    pub(crate) log_level: Option<LevelFilter>,
+    pub(crate) guest_log_level: Option<LevelFilter>,
+    pub(crate) log_sink: Option<LogSink>,
+    pub(crate) keys: Vec<(String, [u32; 4])>,
+    pub(crate) input: Vec<u32>,
+    pub(crate) io_handler: Option<Box<dyn IoHandler>>,
+}
+
+impl ProverOpts {
+    /// Limits the number of cycles the guest may execute. Guests that run
+    /// longer fail with [ExceptionKind::CycleLimitExceeded](crate::ExceptionKind).
+    /// Values above the largest supported trace size are clamped to it.
+    pub fn with_max_cycles(mut self, max_cycles: usize) -> Self {
+        self.max_cycles = Some(max_cycles);
+        self
+    }
+
+    /// Samples the guest call stack every `cycles` cycles while it runs, for
+    /// [Prover::write_profile](crate::Prover::write_profile). Stacks are only
+    /// complete if the guest was built with `-C force-frame-pointers=yes`.
+    pub fn with_sample_interval(mut self, cycles: u32) -> Self {
+        self.sample_interval = Some(cycles);
+        self
+    }
+
+    /// Sets the C++ prover log level, see [set_log_level](crate::set_log_level).
+    /// Note that this level is global to the process.
+    pub fn with_log_level(mut self, level: LevelFilter) -> Self {
+        self.log_level = Some(level);
+        self
+    }
+
+    /// Limits the records the guest emits through the `log` crate, which
+    /// defaults to `Info`. Records above this level are discarded by the guest
+    /// without being formatted. This does not affect `print!` and friends.
+    pub fn with_guest_log_level(mut self, level: LevelFilter) -> Self {
+        self.guest_log_level = Some(level);
+        self
+    }
+
+    /// Sends guest log lines to `sink` instead of the `log` crate.
+    pub fn with_log_sink<F>(mut self, sink: F) -> Self
+    where
+        F: FnMut(u32, &str) + Send + 'static,
+    {
+        self.log_sink = Some(Box::new(sink));
+        self
+    }
+
+    /// Makes `key` available to the guest under `name`.
+    pub fn with_key(mut self, name: &str, key: [u32; 4]) -> Self {
+        self.keys.push((name.to_string(), key));
+        self
+    }
+
+    /// Appends `slice` to the input the guest reads with `env::read`.
+    pub fn with_input(mut self, slice: &[u32]) -> Self {
+        self.input.extend_from_slice(slice);
+        self
+    }
+
+    /// Observes guest writes and commits as they happen.
+    pub fn with_io_handler<H>(mut self, handler: H) -> Self
+    where
+        H: IoHandler + 'static,
+    {
+        self.io_handler = Some(Box::new(handler));
+        self
+    }
+}
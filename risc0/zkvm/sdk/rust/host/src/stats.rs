@@ -0,0 +1,35 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+/// Cycle counts of a guest profiling span, summed over every time the guest
+/// entered it. See `zkvm_guest::profile::span`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SpanStats {
+    /// How many times the span was exited.
+    pub count: u64,
+    /// The cycles spent inside the span, including those of nested spans.
+    /// Recursive entries are counted each time.
+    pub cycles: u64,
+}
+
+/// Statistics collected while running a guest.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RunStats {
+    pub spans: BTreeMap<String, SpanStats>,
+    /// The most bytes of stack the guest had in use at once. A guest that
+    /// runs out of stack fails with a fault instead.
+    pub max_stack_usage: u32,
+}
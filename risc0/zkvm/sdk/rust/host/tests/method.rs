@@ -0,0 +1,52 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+
+use zkvm_host::{ExceptionKind, Method};
+
+const MAIN_PATH: &str = "risc0/zkvm/sdk/rust/guest/test_main";
+const SHA_PATH: &str = "risc0/zkvm/sdk/rust/guest/test_sha";
+
+fn load_method_id(path: &str) -> &'static [u8] {
+    Box::leak(fs::read(format!("{}.id", path)).unwrap().into_boxed_slice())
+}
+
+#[test]
+fn prove_and_verify() {
+    let divide = Method::<u32, (), u32>::new(MAIN_PATH, load_method_id(MAIN_PATH));
+    let receipt = divide.prove(&5).unwrap();
+    assert_eq!(receipt.verify().unwrap(), 20);
+
+    // A receipt received from elsewhere verifies the same way.
+    let receipt = divide.receipt(receipt.into_receipt());
+    assert_eq!(receipt.verify().unwrap(), 20);
+}
+
+#[test]
+fn rejects_other_method_id() {
+    let divide = Method::<u32, (), u32>::new(MAIN_PATH, load_method_id(SHA_PATH));
+    let receipt = divide.prove(&5).unwrap();
+    let err = receipt.verify().unwrap_err();
+    assert_eq!(err.kind(), ExceptionKind::Other);
+    assert!(err.what().contains("verification failed"), "{}", err);
+}
+
+#[test]
+fn rejects_other_journal_type() {
+    let divide = Method::<u32, (), (u32, u32)>::new(MAIN_PATH, load_method_id(MAIN_PATH));
+    let receipt = divide.prove(&5).unwrap();
+    let err = receipt.verify().unwrap_err();
+    assert!(err.what().contains("Failed to decode journal"), "{}", err);
+}
@@ -0,0 +1,57 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runs proofs on the tokio blocking pool, so that they do not stall the
+//! async runtime.
+//!
+//! This is a crate of its own rather than a `tokio` feature of `zkvm_host`.
+//! Bazel builds `zkvm_host` once, with one set of features, so a feature
+//! would pull tokio into every user of the host crate.
+
+use zkvm_host::{CancelHandle, Exception, ExceptionKind, Prover, Receipt, Result};
+
+/// Runs `prover` on the tokio blocking pool. The prover is handed back
+/// alongside the receipt so that its output can still be read.
+///
+/// Dropping the returned future cancels the proof; the blocking task notices
+/// shortly afterwards and fails with [ExceptionKind::Cancelled].
+pub async fn prove_async(prover: Prover) -> Result<(Prover, Receipt)> {
+    let mut guard = CancelOnDrop(Some(prover.cancel_handle()));
+    let task = tokio::task::spawn_blocking(move || {
+        let receipt = prover.run()?;
+        Ok((prover, receipt))
+    });
+    let result = task.await;
+    guard.0 = None;
+    match result {
+        Ok(result) => result,
+        Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+        Err(err) => Err(Exception::with_kind(
+            ExceptionKind::Cancelled,
+            err.to_string(),
+        )),
+    }
+}
+
+/// Cancels the associated proof when dropped, unless it has been disarmed by
+/// taking the handle out first.
+struct CancelOnDrop(Option<CancelHandle>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if let Some(handle) = self.0.take() {
+            handle.cancel();
+        }
+    }
+}
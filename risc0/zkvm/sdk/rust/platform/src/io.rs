@@ -0,0 +1,30 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The addresses the guest writes descriptors to in order to ask the host for
+//! I/O. The descriptor layouts are in `risc0/zkvm/platform/io.h`.
+
+pub const GPIO_SHA: usize = 0x001C_0000;
+pub const GPIO_WRITE: usize = 0x001C_0004;
+pub const GPIO_COMMIT: usize = 0x001C_0008;
+pub const GPIO_FAULT: usize = 0x001C_000C;
+pub const GPIO_LOG: usize = 0x001C_0010;
+pub const GPIO_GET_KEY: usize = 0x001C_0014;
+pub const GPIO_GET_LOG_LEVEL: usize = 0x001C_0018;
+pub const GPIO_GET_CYCLE: usize = 0x001C_001C;
+pub const GPIO_SPAN: usize = 0x001C_0020;
+
+/// Scratch space in the GPIO region where the guest builds the descriptor it
+/// passes to one of the ports above.
+pub const GPIO_DESC: usize = 0x001D_0000;
@@ -0,0 +1,65 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The memory map of the guest. Methods can resize [STACK], [HEAP], [INPUT],
+//! [OUTPUT] and [COMMIT] when they are linked, which moves the regions laid out
+//! after them, see `risc0/zkvm/platform/risc0.ld`. The constants here are the
+//! default layout.
+
+/// A contiguous range of guest memory.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Region {
+    start: usize,
+    len_bytes: usize,
+}
+
+impl Region {
+    pub const fn new(start: usize, len_bytes: usize) -> Self {
+        Self { start, len_bytes }
+    }
+
+    pub const fn start(&self) -> usize {
+        self.start
+    }
+
+    pub const fn len_bytes(&self) -> usize {
+        self.len_bytes
+    }
+
+    pub const fn len_words(&self) -> usize {
+        self.len_bytes / crate::WORD_SIZE
+    }
+
+    pub const fn end(&self) -> usize {
+        self.start + self.len_bytes
+    }
+}
+
+pub const MEM_BITS: usize = 20;
+pub const MEM_SIZE: usize = (1 << MEM_BITS) * crate::WORD_SIZE;
+
+const KB: usize = 1024;
+const MB: usize = 1024 * KB;
+
+pub const STACK: Region = Region::new(0x0000_0000, 256 * KB);
+pub const DATA: Region = Region::new(0x0004_0000, 256 * KB);
+pub const HEAP: Region = Region::new(0x0008_0000, MB);
+pub const INPUT: Region = Region::new(0x0018_0000, 256 * KB);
+pub const GPIO: Region = Region::new(0x001C_0000, 256 * KB);
+pub const PROG: Region = Region::new(0x0020_0000, MB);
+pub const SHA: Region = Region::new(0x0030_0000, 256 * KB);
+/// Write-once memory, which holds [OUTPUT] and [COMMIT].
+pub const WOM: Region = Region::new(0x0034_0000, 512 * KB);
+pub const OUTPUT: Region = Region::new(0x0034_0000, 256 * KB);
+pub const COMMIT: Region = Region::new(0x0038_0000, 256 * KB);
@@ -0,0 +1,189 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use zkvm_platform::{
+    io,
+    memory::{self, Region},
+};
+
+const MEMORY_H: &str = include_str!("../../../../platform/memory.h");
+const IO_H: &str = include_str!("../../../../platform/io.h");
+const LINKER_SCRIPT: &str = include_str!("../../../../platform/risc0.ld");
+
+const REGIONS: &[(&str, Region)] = &[
+    ("Stack", memory::STACK),
+    ("Data", memory::DATA),
+    ("Heap", memory::HEAP),
+    ("Input", memory::INPUT),
+    ("GPIO", memory::GPIO),
+    ("Prog", memory::PROG),
+    ("SHA", memory::SHA),
+    ("WOM", memory::WOM),
+    ("Output", memory::OUTPUT),
+    ("Commit", memory::COMMIT),
+];
+
+fn parse_int(s: &str) -> usize {
+    let s = s.trim();
+    match s.strip_prefix("0x") {
+        Some(hex) => usize::from_str_radix(hex, 16),
+        None => s.parse(),
+    }
+    .unwrap_or_else(|_| panic!("not an integer: {:?}", s))
+}
+
+// Returns the values of the `constexpr size_t` definitions in `header` that
+// are plain integer literals.
+fn constants(header: &str) -> BTreeMap<&str, usize> {
+    header
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("constexpr size_t "))
+        .filter_map(|def| {
+            let (name, value) = def.strip_suffix(';')?.split_once(" = ")?;
+            value
+                .starts_with(|c: char| c.is_ascii_digit())
+                .then(|| (name, parse_int(value)))
+        })
+        .collect()
+}
+
+// Parses the `MEM_REGION(name, start, len)` lines of memory.h, where `len`
+// is one of the size constants, optionally multiplied by an integer.
+fn header_regions() -> BTreeMap<&'static str, Region> {
+    let sizes = constants(MEMORY_H);
+    MEMORY_H
+        .lines()
+        .filter_map(|line| line.strip_prefix("MEM_REGION(")?.strip_suffix(')'))
+        .map(|args| {
+            let args: Vec<_> = args.split(',').map(str::trim).collect();
+            let len = match args[2].split_once('*') {
+                Some((name, factor)) => sizes[name.trim()] * parse_int(factor),
+                None => sizes[args[2]],
+            };
+            (args[0], Region::new(parse_int(args[1]), len))
+        })
+        .collect()
+}
+
+// Parses a linker script size such as `0x1000`, `256K` or `1M`.
+fn parse_size(s: &str) -> usize {
+    let s = s.trim();
+    if let Some(kb) = s.strip_suffix('K') {
+        parse_int(kb) * 1024
+    } else if let Some(mb) = s.strip_suffix('M') {
+        parse_int(mb) * 1024 * 1024
+    } else {
+        parse_int(s)
+    }
+}
+
+// Parses the `PROVIDE(__<region>_size = len);` defaults of the resizable
+// regions.
+fn linker_default_sizes() -> BTreeMap<&'static str, usize> {
+    LINKER_SCRIPT
+        .lines()
+        .filter_map(|line| line.strip_prefix("PROVIDE(__")?.strip_suffix(");"))
+        .map(|def| {
+            let (name, len) = def.split_once("_size = ").unwrap();
+            (name, parse_size(len))
+        })
+        .collect()
+}
+
+// Parses the `name : ORIGIN = start, LENGTH = len` lines of the MEMORY
+// command of the linker script, keeping the expressions as written.
+fn linker_regions() -> BTreeMap<&'static str, (&'static str, &'static str)> {
+    let (_, memory) = LINKER_SCRIPT.split_once("MEMORY {").unwrap();
+    let (memory, _) = memory.split_once('}').unwrap();
+    memory
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, def)| {
+            let name = name.split_whitespace().next().unwrap();
+            let (origin, length) = def.split_once(", LENGTH =").unwrap();
+            let origin = origin.trim().strip_prefix("ORIGIN =").unwrap();
+            (name, (origin.trim(), length.trim()))
+        })
+        .collect()
+}
+
+#[test]
+fn memory_header() {
+    let header = header_regions();
+    assert_eq!(header.len(), REGIONS.len());
+    for (name, region) in REGIONS {
+        assert_eq!(header.get(name), Some(region), "region {}", name);
+    }
+
+    let sizes = constants(MEMORY_H);
+    assert_eq!(sizes["kMemBits"], memory::MEM_BITS);
+}
+
+#[test]
+fn linker_script() {
+    let defaults = linker_default_sizes();
+    assert_eq!(defaults.len(), 5);
+    for (name, region) in REGIONS {
+        if let Some(len) = defaults.get(name.to_lowercase().as_str()) {
+            assert_eq!(*len, region.len_bytes(), "region {}", name);
+        }
+    }
+
+    // The regions at fixed addresses.
+    let linker = linker_regions();
+    for (name, region) in [
+        ("gpio", memory::GPIO),
+        ("prog", memory::PROG),
+        ("sha", memory::SHA),
+    ] {
+        let (origin, length) = linker[name];
+        assert_eq!(parse_int(origin), region.start(), "region {}", name);
+        assert_eq!(parse_size(length), region.len_bytes(), "region {}", name);
+    }
+    assert_eq!(parse_size(linker["data"].1), memory::DATA.len_bytes());
+    assert_eq!(parse_int(linker["wom"].0), memory::WOM.start());
+
+    // The other regions follow each other, as the linker script lays them out.
+    assert_eq!(memory::STACK.start(), 0);
+    assert_eq!(memory::STACK.end(), memory::DATA.start());
+    assert_eq!(memory::DATA.end(), memory::HEAP.start());
+    assert_eq!(memory::HEAP.end(), memory::INPUT.start());
+    assert!(memory::INPUT.end() <= memory::GPIO.start());
+    assert_eq!(memory::WOM.start(), memory::OUTPUT.start());
+    assert_eq!(memory::OUTPUT.end(), memory::COMMIT.start());
+    assert_eq!(memory::COMMIT.end(), memory::WOM.end());
+    assert!(memory::WOM.end() <= memory::MEM_SIZE);
+}
+
+#[test]
+fn io_header() {
+    let expected = BTreeMap::from([
+        ("kGPIO_SHA", io::GPIO_SHA),
+        ("kGPIO_Write", io::GPIO_WRITE),
+        ("kGPIO_Commit", io::GPIO_COMMIT),
+        ("kGPIO_Fault", io::GPIO_FAULT),
+        ("kGPIO_Log", io::GPIO_LOG),
+        ("kGPIO_GetKey", io::GPIO_GET_KEY),
+        ("kGPIO_GetLogLevel", io::GPIO_GET_LOG_LEVEL),
+        ("kGPIO_GetCycle", io::GPIO_GET_CYCLE),
+        ("kGPIO_Span", io::GPIO_SPAN),
+        ("kGPIO_Desc", io::GPIO_DESC),
+    ]);
+    assert_eq!(constants(IO_H), expected);
+    for addr in expected.values() {
+        assert!(memory::GPIO.start() <= *addr && *addr < memory::GPIO.end());
+    }
+}
@@ -12,6 +12,18 @@ pub fn from_slice<'a, T: Deserialize<'a>>(slice: &'a [u32]) -> Result<T> {
     T::deserialize(&mut deserializer)
 }
 
+/// Like [from_slice], but fails with [Error::DeserializeTrailingData] unless
+/// decoding `T` uses up all of `slice`.
+pub fn from_slice_exact<'a, T: Deserialize<'a>>(slice: &'a [u32]) -> Result<T> {
+    let mut deserializer = Deserializer::new(slice);
+    let value = T::deserialize(&mut deserializer)?;
+    if !deserializer.is_empty() {
+        return Err(Error::DeserializeTrailingData);
+    }
+    Ok(value)
+}
+
+#[derive(Clone)]
 pub struct Deserializer<'de> {
     slice: &'de [u32],
 }
@@ -113,6 +125,16 @@ impl<'de> Deserializer<'de> {
         Deserializer { slice }
     }
 
+    /// Returns how many words are left to read.
+    pub fn remaining(&self) -> usize {
+        self.slice.len()
+    }
+
+    /// Returns whether all of the input has been read.
+    pub fn is_empty(&self) -> bool {
+        self.slice.is_empty()
+    }
+
     fn try_take_word(&mut self) -> Result<u32> {
         if self.slice.len() >= 1 {
             let (head, tail) = self.slice.split_first().unwrap();
@@ -234,18 +256,18 @@ impl<'de, 'a> serde::Deserializer<'de> for &'a mut Deserializer<'de> {
         visitor.visit_u64(self.try_take_dword()?)
     }
 
-    fn deserialize_f32<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        Err(Error::NotSupported)
+        visitor.visit_f32(f32::from_bits(self.try_take_word()?))
     }
 
-    fn deserialize_f64<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        Err(Error::NotSupported)
+        visitor.visit_f64(f64::from_bits(self.try_take_dword()?))
     }
 
     fn deserialize_char<V>(self, visitor: V) -> Result<V::Value>
@@ -484,4 +506,35 @@ mod test {
         };
         assert_eq!(expected, from_slice(&words).unwrap());
     }
+
+    #[test]
+    fn test_exact() {
+        let words = [1, 2, 3];
+        assert_eq!(
+            (1, 2, 3),
+            from_slice_exact::<(u32, u32, u32)>(&words).unwrap()
+        );
+        assert_eq!((1, 2), from_slice::<(u32, u32)>(&words).unwrap());
+        assert_eq!(
+            Error::DeserializeTrailingData,
+            from_slice_exact::<(u32, u32)>(&words).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_remaining() {
+        use serde::Deserialize;
+
+        let words = [1, 2, 3];
+        let mut deserializer = Deserializer::new(&words);
+        assert_eq!(3, deserializer.remaining());
+        assert_eq!(1, u32::deserialize(&mut deserializer).unwrap());
+        assert_eq!(2, deserializer.remaining());
+        assert_eq!(0x3_00000002, u64::deserialize(&mut deserializer).unwrap());
+        assert_eq!(
+            Error::DeserializeUnexpectedEnd,
+            u32::deserialize(&mut deserializer).unwrap_err()
+        );
+        assert!(deserializer.is_empty());
+    }
 }
@@ -9,6 +9,7 @@ pub enum Error {
     DeserializeBadChar,
     DeserializeBadOption,
     DeserializeBadUtf8,
+    DeserializeTrailingData,
     DeserializeUnexpectedEnd,
     NotSupported,
     SerializeBufferFull,
@@ -24,6 +25,7 @@ impl Display for Error {
             Self::DeserializeBadChar => "Found an invalid unicode char",
             Self::DeserializeBadOption => "Found an Option discriminant that wasn't 0 or 1",
             Self::DeserializeBadUtf8 => "Tried to parse invalid utf-8",
+            Self::DeserializeTrailingData => "Found data left over after deserialization",
             Self::DeserializeUnexpectedEnd => "Unexpected end during deserialization",
             Self::NotSupported => "Not supported",
             Self::SerializeBufferFull => "The serialize buffer is full",
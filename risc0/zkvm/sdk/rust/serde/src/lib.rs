@@ -4,14 +4,119 @@ extern crate alloc;
 
 mod deserializer;
 mod err;
+pub mod packed;
 mod serializer;
+pub mod words;
 
-pub use deserializer::{from_slice, Deserializer};
+pub use deserializer::{from_slice, from_slice_exact, Deserializer};
+pub use err::{Error, Result};
 pub use serializer::{to_slice, to_vec, AllocVec, Serializer, Slice};
 
+const WORD_SIZE: usize = core::mem::size_of::<u32>();
+
 /// Align the given address `addr` upwards to alignment `align`.
 ///
 /// Requires that `align` is a power of two.
 fn align_up(addr: usize, align: usize) -> usize {
     (addr + align - 1) & !(align - 1)
 }
+
+#[cfg(test)]
+mod test {
+    use alloc::{
+        collections::BTreeMap,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+    use core::fmt::Debug;
+
+    use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+    use super::*;
+
+    fn round_trip<T>(value: T)
+    where
+        T: Serialize + DeserializeOwned + PartialEq + Debug,
+    {
+        let words = to_vec(&value).unwrap();
+        assert_eq!(value, from_slice::<T>(&words).unwrap());
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Shape {
+        Empty,
+        Circle(u32),
+        Rect(u32, u32),
+        Polygon { sides: u8, points: Vec<(i32, i32)> },
+    }
+
+    #[test]
+    fn test_primitives() {
+        round_trip((true, -1i8, 2u8, -3i16, 4u16, -5i32, 6u32, -7i64, 8u64));
+        round_trip((1.5f32, -2.25f64, f64::MAX));
+        round_trip(('a', 'é', '💖'));
+        round_trip(("abc".to_string(), String::new()));
+        round_trip((Some(3u32), None::<u32>, ()));
+    }
+
+    #[test]
+    fn test_vec() {
+        round_trip(Vec::<u32>::new());
+        round_trip(vec![1u32, 2, 3]);
+        round_trip(vec![vec![1u8], vec![], vec![2, 3]]);
+        round_trip(vec!["a".to_string(), "bc".to_string()]);
+        round_trip(vec![Some(1u64), None]);
+    }
+
+    #[test]
+    fn test_map() {
+        let mut map = BTreeMap::new();
+        map.insert("one".to_string(), vec![1u32]);
+        map.insert("two".to_string(), vec![2, 2]);
+        round_trip(map);
+        round_trip(BTreeMap::<u32, u32>::new());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hash_map() {
+        let mut map = std::collections::HashMap::new();
+        map.insert(1u32, "one".to_string());
+        map.insert(2u32, "two".to_string());
+        round_trip(map);
+    }
+
+    #[test]
+    fn test_enum() {
+        round_trip(vec![
+            Shape::Empty,
+            Shape::Circle(5),
+            Shape::Rect(2, 3),
+            Shape::Polygon {
+                sides: 3,
+                points: vec![(0, 0), (1, 0), (0, -1)],
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_trailing_fields() {
+        // Collections in the middle of a struct must not swallow the fields
+        // after them.
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Test {
+            list: Vec<u32>,
+            map: BTreeMap<u32, bool>,
+            last: u32,
+        }
+
+        let mut map = BTreeMap::new();
+        map.insert(7, true);
+        round_trip(Test {
+            list: vec![1, 2],
+            map,
+            last: 9,
+        });
+    }
+}
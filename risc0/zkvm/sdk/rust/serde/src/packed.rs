@@ -0,0 +1,167 @@
+//! Packs byte arrays and vectors four bytes to a word, instead of widening
+//! every byte to a word of its own. Opt in per field with
+//! `#[serde(with = "zkvm_serde::packed")]`:
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Message {
+//!     #[serde(with = "zkvm_serde::packed")]
+//!     hash: [u8; 32],
+//!     #[serde(with = "zkvm_serde::packed")]
+//!     body: Vec<u8>,
+//! }
+//! ```
+//!
+//! A `[u8; N]` is written as its bytes, with the last word padded with zeros.
+//! A `Vec<u8>` is written the same way after its length in bytes.
+
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserializer, Serializer,
+};
+
+use crate::{align_up, WORD_SIZE};
+
+/// Byte containers that can be packed, see the [module](self) documentation.
+pub trait PackedBytes: Sized {
+    fn serialize_packed<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+
+    fn deserialize_packed<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+pub fn serialize<T: PackedBytes, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value.serialize_packed(serializer)
+}
+
+pub fn deserialize<'de, T: PackedBytes, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    T::deserialize_packed(deserializer)
+}
+
+fn words<const N: usize>() -> usize {
+    align_up(N, WORD_SIZE) / WORD_SIZE
+}
+
+impl<const N: usize> PackedBytes for [u8; N] {
+    // The length is part of the type, so the words are written as a tuple
+    // without one.
+    fn serialize_packed<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(words::<N>())?;
+        for chunk in self.chunks(WORD_SIZE) {
+            let mut word = [0; WORD_SIZE];
+            word[..chunk.len()].copy_from_slice(chunk);
+            tuple.serialize_element(&u32::from_le_bytes(word))?;
+        }
+        tuple.end()
+    }
+
+    fn deserialize_packed<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_tuple(words::<N>(), ArrayVisitor::<N>)
+    }
+}
+
+struct ArrayVisitor<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for ArrayVisitor<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{} bytes packed into {} words", N, words::<N>())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = [0; N];
+        for (idx, chunk) in bytes.chunks_mut(WORD_SIZE).enumerate() {
+            let word: u32 = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(idx, &self))?;
+            chunk.copy_from_slice(&word.to_le_bytes()[..chunk.len()]);
+        }
+        Ok(bytes)
+    }
+}
+
+impl PackedBytes for Vec<u8> {
+    fn serialize_packed<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self)
+    }
+
+    fn deserialize_packed<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_byte_buf(VecVisitor(PhantomData))
+    }
+}
+
+struct VecVisitor<'de>(PhantomData<&'de ()>);
+
+impl<'de> Visitor<'de> for VecVisitor<'de> {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("packed bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{vec, vec::Vec};
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::{err::Error, from_slice, to_vec};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Test {
+        #[serde(with = "crate::packed")]
+        hash: [u8; 6],
+        #[serde(with = "crate::packed")]
+        empty: [u8; 0],
+        #[serde(with = "crate::packed")]
+        body: Vec<u8>,
+        last: u8,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let input = Test {
+            hash: [1, 2, 3, 4, 5, 6],
+            empty: [],
+            body: vec![7, 8, 9, 10, 11],
+            last: 12,
+        };
+        let expected = [
+            0x04030201, 0x00000605, // hash
+            5, 0x0a090807, 0x0000000b, // body
+            12,
+        ];
+        let words = to_vec(&input).unwrap();
+        assert_eq!(expected, words[..]);
+        assert_eq!(input, from_slice(&words).unwrap());
+    }
+
+    #[test]
+    fn test_truncated() {
+        let words = [0x04030201];
+        assert_eq!(
+            Error::DeserializeUnexpectedEnd,
+            from_slice::<Test>(&words).unwrap_err()
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use alloc::string::ToString;
 use core::{mem, slice};
 
 use serde::Serialize;
@@ -5,10 +6,9 @@ use serde::Serialize;
 use crate::{
     align_up,
     err::{Error, Result},
+    WORD_SIZE,
 };
 
-const WORD_SIZE: usize = mem::size_of::<u32>();
-
 pub fn to_slice<'a, 'b, T>(value: &'b T, buf: &'a mut [u32]) -> Result<&'a [u32]>
 where
     T: Serialize + ?Sized,
@@ -60,11 +60,11 @@ impl<W: StreamWriter> Serializer<W> {
 impl<'a, W: StreamWriter> serde::ser::Serializer for &'a mut Serializer<W> {
     type Ok = ();
     type Error = Error;
-    type SerializeSeq = Self;
+    type SerializeSeq = Compound<'a, W>;
     type SerializeTuple = Self;
     type SerializeTupleStruct = Self;
     type SerializeTupleVariant = Self;
-    type SerializeMap = Self;
+    type SerializeMap = Compound<'a, W>;
     type SerializeStruct = Self;
     type SerializeStructVariant = Self;
 
@@ -72,11 +72,11 @@ impl<'a, W: StreamWriter> serde::ser::Serializer for &'a mut Serializer<W> {
         false
     }
 
-    fn collect_str<T>(self, _: &T) -> Result<()>
+    fn collect_str<T>(self, value: &T) -> Result<()>
     where
         T: core::fmt::Display + ?Sized,
     {
-        panic!("collect_str")
+        self.serialize_str(&value.to_string())
     }
 
     fn serialize_bool(self, v: bool) -> Result<()> {
@@ -115,12 +115,12 @@ impl<'a, W: StreamWriter> serde::ser::Serializer for &'a mut Serializer<W> {
         self.stream.try_push_dword(v)
     }
 
-    fn serialize_f32(self, _v: f32) -> Result<()> {
-        Err(Error::NotSupported)
+    fn serialize_f32(self, v: f32) -> Result<()> {
+        self.serialize_u32(v.to_bits())
     }
 
-    fn serialize_f64(self, _v: f64) -> Result<()> {
-        Err(Error::NotSupported)
+    fn serialize_f64(self, v: f64) -> Result<()> {
+        self.serialize_u64(v.to_bits())
     }
 
     fn serialize_char(self, v: char) -> Result<()> {
@@ -190,8 +190,8 @@ impl<'a, W: StreamWriter> serde::ser::Serializer for &'a mut Serializer<W> {
         value.serialize(self)
     }
 
-    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
-        Ok(self)
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Compound::new(self, len)
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
@@ -209,15 +209,16 @@ impl<'a, W: StreamWriter> serde::ser::Serializer for &'a mut Serializer<W> {
     fn serialize_tuple_variant(
         self,
         _name: &'static str,
-        _variant_index: u32,
+        variant_index: u32,
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant> {
+        self.stream.try_push_word(variant_index)?;
         Ok(self)
     }
 
-    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
-        Ok(self)
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
+        Compound::new(self, len)
     }
 
     fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
@@ -236,7 +237,55 @@ impl<'a, W: StreamWriter> serde::ser::Serializer for &'a mut Serializer<W> {
     }
 }
 
-impl<'a, W: StreamWriter> serde::ser::SerializeSeq for &'a mut Serializer<W> {
+/// Serializes the elements of a sequence or the entries of a map after their
+/// count. When the count is not known up front, the elements are buffered
+/// until [end](serde::ser::SerializeSeq::end) so that it can be written first.
+pub struct Compound<'a, W: StreamWriter> {
+    serializer: &'a mut Serializer<W>,
+    buffer: Option<(Serializer<AllocVec>, u32)>,
+}
+
+impl<'a, W: StreamWriter> Compound<'a, W> {
+    fn new(serializer: &'a mut Serializer<W>, len: Option<usize>) -> Result<Self> {
+        let buffer = match len {
+            Some(len) => {
+                serializer.stream.try_push_word(len as u32)?;
+                None
+            }
+            None => Some((Serializer::new(AllocVec::new()), 0)),
+        };
+        Ok(Compound { serializer, buffer })
+    }
+
+    fn serialize<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        match &mut self.buffer {
+            Some((buffer, _)) => value.serialize(buffer),
+            None => value.serialize(&mut *self.serializer),
+        }
+    }
+
+    fn count(&mut self) {
+        if let Some((_, count)) = &mut self.buffer {
+            *count += 1;
+        }
+    }
+
+    fn finish(self) -> Result<()> {
+        if let Some((mut buffer, count)) = self.buffer {
+            let stream = &mut self.serializer.stream;
+            stream.try_push_word(count)?;
+            for word in buffer.release()? {
+                stream.try_push_word(word)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a, W: StreamWriter> serde::ser::SerializeSeq for Compound<'a, W> {
     type Ok = ();
     type Error = Error;
 
@@ -244,11 +293,12 @@ impl<'a, W: StreamWriter> serde::ser::SerializeSeq for &'a mut Serializer<W> {
     where
         T: serde::Serialize + ?Sized,
     {
-        value.serialize(&mut **self)
+        self.count();
+        self.serialize(value)
     }
 
     fn end(self) -> Result<()> {
-        Ok(())
+        self.finish()
     }
 }
 
@@ -300,7 +350,7 @@ impl<'a, W: StreamWriter> serde::ser::SerializeTupleVariant for &'a mut Serializ
     }
 }
 
-impl<'a, W: StreamWriter> serde::ser::SerializeMap for &'a mut Serializer<W> {
+impl<'a, W: StreamWriter> serde::ser::SerializeMap for Compound<'a, W> {
     type Ok = ();
     type Error = Error;
 
@@ -308,18 +358,19 @@ impl<'a, W: StreamWriter> serde::ser::SerializeMap for &'a mut Serializer<W> {
     where
         T: serde::Serialize + ?Sized,
     {
-        key.serialize(&mut **self)
+        self.count();
+        self.serialize(key)
     }
 
     fn serialize_value<T>(&mut self, value: &T) -> Result<()>
     where
         T: serde::Serialize + ?Sized,
     {
-        value.serialize(&mut **self)
+        self.serialize(value)
     }
 
     fn end(self) -> Result<()> {
-        Ok(())
+        self.finish()
     }
 }
 
@@ -455,16 +506,14 @@ impl StreamWriter for AllocVec {
     }
 
     fn release(&mut self) -> Result<Self::Output> {
-        let ret = self.0.clone();
-        self.0 = alloc::vec::Vec::new();
-        Ok(ret)
+        Ok(mem::take(&mut self.0))
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use alloc::string::String;
+    use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
     use serde::Serialize;
 
     #[test]
@@ -527,4 +576,38 @@ mod test {
         let buf: &mut [u32] = &mut [0; 256];
         assert_eq!(expected, to_slice(&input, buf).unwrap());
     }
+
+    #[test]
+    fn test_seq() {
+        let input: Vec<u32> = vec![1, 2, 3];
+        assert_eq!([3, 1, 2, 3], to_vec(&input).unwrap()[..]);
+    }
+
+    #[test]
+    fn test_seq_unknown_len() {
+        // Filtering hides the length from serde, so the elements are buffered.
+        struct Odd<'a>(&'a [u32]);
+
+        impl<'a> Serialize for Odd<'a> {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> core::result::Result<S::Ok, S::Error> {
+                serializer.collect_seq(self.0.iter().filter(|x| *x % 2 == 1))
+            }
+        }
+
+        let input = Odd(&[1, 2, 3, 4, 5]);
+        assert_eq!([3, 1, 3, 5], to_vec(&input).unwrap()[..]);
+        let buf: &mut [u32] = &mut [0; 3];
+        assert_eq!(Err(Error::SerializeBufferFull), to_slice(&input, buf));
+    }
+
+    #[test]
+    fn test_map() {
+        let mut input = BTreeMap::new();
+        input.insert(1u32, 10u32);
+        input.insert(2u32, 20u32);
+        assert_eq!([2, 1, 10, 2, 20], to_vec(&input).unwrap()[..]);
+    }
 }
@@ -0,0 +1,228 @@
+//! Borrows words straight out of the input instead of copying them.
+//!
+//! A `Vec<u32>` is deserialized one element at a time into a fresh
+//! allocation, which the guest's bump allocator never gets back. A
+//! [WordSlice] instead points into the slice the [Deserializer] reads from:
+//!
+//! ```
+//! use zkvm_serde::{from_slice, to_vec, words::WordSlice};
+//!
+//! let words = to_vec(&WordSlice::new(&[1u32, 2, 3])).unwrap();
+//! let slice: WordSlice = from_slice(&words).unwrap();
+//! assert_eq!(&words[1..], &slice[..]);
+//! ```
+//!
+//! [WordSlice] and [WordRef], its counterpart for a single value, are written
+//! as bytes, that is their length in bytes followed by the words, so they
+//! cannot be read back as a `Vec<u32>`.
+//!
+//! [Deserializer]: crate::Deserializer
+
+use core::{fmt, marker::PhantomData, mem, ops::Deref, slice};
+
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// Types that are nothing but words, so that any word-aligned run of
+/// `size_of::<T>()` bytes is a valid `T`.
+///
+/// # Safety
+///
+/// The type must have the alignment of `u32`, no padding, and every bit
+/// pattern must be valid, as is the case for a `#[repr(C)]` struct whose
+/// fields are all `u32` or other `Words`.
+///
+/// Zero-sized types such as `[u32; 0]` are never read back, since a run of
+/// no bytes does not say how many of them there were.
+pub unsafe trait Words: Copy {}
+
+unsafe impl Words for u32 {}
+
+unsafe impl<const N: usize> Words for [u32; N] {}
+
+/// A slice of words borrowed from the input, see the [module](self)
+/// documentation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct WordSlice<'a, T: Words = u32>(&'a [T]);
+
+impl<'a, T: Words> WordSlice<'a, T> {
+    pub fn new(slice: &'a [T]) -> Self {
+        WordSlice(slice)
+    }
+
+    pub fn as_slice(&self) -> &'a [T] {
+        self.0
+    }
+}
+
+impl<'a, T: Words> Deref for WordSlice<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.0
+    }
+}
+
+impl<'a, T: Words> Serialize for WordSlice<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(as_bytes(self.0))
+    }
+}
+
+impl<'de: 'a, 'a, T: Words> Deserialize<'de> for WordSlice<'a, T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_bytes(BorrowVisitor(PhantomData))
+            .map(WordSlice)
+    }
+}
+
+/// A single value borrowed from the input, such as a `#[repr(C)]` struct of
+/// words, see the [module](self) documentation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct WordRef<'a, T: Words>(&'a T);
+
+impl<'a, T: Words> WordRef<'a, T> {
+    pub fn new(value: &'a T) -> Self {
+        WordRef(value)
+    }
+
+    pub fn get(&self) -> &'a T {
+        self.0
+    }
+}
+
+impl<'a, T: Words> Deref for WordRef<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.0
+    }
+}
+
+impl<'a, T: Words> Serialize for WordRef<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(as_bytes(slice::from_ref(self.0)))
+    }
+}
+
+impl<'de: 'a, 'a, T: Words> Deserialize<'de> for WordRef<'a, T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let slice: &[T] = deserializer.deserialize_bytes(BorrowVisitor(PhantomData))?;
+        match slice {
+            [value] => Ok(WordRef(value)),
+            _ => Err(de::Error::invalid_length(slice.len(), &"exactly one value")),
+        }
+    }
+}
+
+fn as_bytes<T: Words>(slice: &[T]) -> &[u8] {
+    unsafe { slice::from_raw_parts(slice.as_ptr().cast(), mem::size_of_val(slice)) }
+}
+
+struct BorrowVisitor<'a, T>(PhantomData<&'a [T]>);
+
+impl<'de: 'a, 'a, T: Words> Visitor<'de> for BorrowVisitor<'a, T> {
+    type Value = &'a [T];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("word-aligned bytes borrowed from the input")
+    }
+
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        let size = mem::size_of::<T>();
+        if size == 0 {
+            return Err(de::Error::invalid_type(de::Unexpected::Bytes(v), &self));
+        }
+        let aligned = v.as_ptr().align_offset(mem::align_of::<T>()) == 0;
+        if !aligned || !v.chunks_exact(size).remainder().is_empty() {
+            return Err(de::Error::invalid_value(de::Unexpected::Bytes(v), &self));
+        }
+        Ok(unsafe { slice::from_raw_parts(v.as_ptr().cast(), v.len() / size) })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::{err::Error, from_slice, to_vec};
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Point {
+        x: u32,
+        y: u32,
+    }
+
+    unsafe impl Words for Point {}
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Test<'a> {
+        #[serde(borrow)]
+        words: WordSlice<'a>,
+        #[serde(borrow)]
+        points: WordSlice<'a, Point>,
+        #[serde(borrow)]
+        origin: WordRef<'a, Point>,
+        last: u32,
+    }
+
+    #[test]
+    fn test_borrow() {
+        let points = [Point { x: 1, y: 2 }, Point { x: 3, y: 4 }];
+        let origin = Point { x: 0, y: 0 };
+        let input = Test {
+            words: WordSlice::new(&[5, 6, 7]),
+            points: WordSlice::new(&points),
+            origin: WordRef::new(&origin),
+            last: 8,
+        };
+        let words = to_vec(&input).unwrap();
+        assert_eq!(words, vec![12, 5, 6, 7, 16, 1, 2, 3, 4, 8, 0, 0, 8],);
+
+        let output: Test = from_slice(&words).unwrap();
+        assert_eq!(input, output);
+        // The words are the ones in the input, not a copy of them.
+        assert_eq!(output.words.as_ptr(), words[1..].as_ptr());
+        assert_eq!(
+            output.origin.get() as *const Point,
+            words[10..].as_ptr().cast()
+        );
+    }
+
+    #[test]
+    fn test_bad_length() {
+        // 6 bytes is not a whole number of words.
+        let words = [6, 1, 2];
+        assert_eq!(
+            Error::NotSupported,
+            from_slice::<WordSlice>(&words).unwrap_err()
+        );
+        // Two points where one was expected.
+        let words = [16, 1, 2, 3, 4];
+        assert_eq!(
+            Error::NotSupported,
+            from_slice::<WordRef<Point>>(&words).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_zero_sized() {
+        let words = to_vec(&WordSlice::new(&[[0u32; 0]; 3])).unwrap();
+        assert_eq!(words, vec![0]);
+        assert_eq!(
+            Error::NotSupported,
+            from_slice::<WordSlice<[u32; 0]>>(&words).unwrap_err()
+        );
+        assert_eq!(
+            Error::NotSupported,
+            from_slice::<WordRef<[u32; 0]>>(&words).unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The RISC-V circuit, mirroring `risc0/zkvm/verify/riscv.cpp`. The
+//! constraint polynomial is evaluated by interpreting the step table that is
+//! generated from the circuit definition.
+
+use alloc::vec;
+
+use zkvm_core::Digest;
+
+use crate::{fp::Fp, fp4::Fp4, read_iop::ReadIop, step, Error, MethodId, Result, MIN_CYCLES_PO2};
+
+/// The number of registers, including the journal commitment, the guest
+/// leaves in its output.
+const OUTPUT_REGS: usize = 9;
+const ACCUM_MIX_GLOBAL_OFFSET: usize = OUTPUT_REGS * 2;
+const ACCUM_MIX_GLOBAL_SIZE: usize = 20;
+const GLOBAL_SIZE: usize = ACCUM_MIX_GLOBAL_OFFSET + ACCUM_MIX_GLOBAL_SIZE;
+
+/// A single step of the constraint polynomial. `Const`, `Get`, `GetGlobal`
+/// and the arithmetic ops write an Fp4 slot; `Begin`, `AssertZero` and
+/// `Combine` write a constraint slot.
+pub(crate) enum Op {
+    Const(u32, u32),
+    Get(u32, u32),
+    GetGlobal(u32, u32),
+    Begin(u32),
+    AssertZero(u32, u32, u32),
+    Combine(u32, u32, u32, u32),
+    Add(u32, u32, u32),
+    Sub(u32, u32, u32),
+    Mul(u32, u32, u32),
+}
+
+#[derive(Clone, Copy)]
+struct MixState {
+    tot: Fp4,
+    mul: Fp4,
+}
+
+impl Default for MixState {
+    fn default() -> Self {
+        MixState {
+            tot: Fp4::default(),
+            mul: Fp4::one(),
+        }
+    }
+}
+
+impl MixState {
+    fn assert_zero(&self, val: Fp4, mix: Fp4) -> Self {
+        MixState {
+            tot: self.tot + self.mul * val,
+            mul: self.mul * mix,
+        }
+    }
+
+    fn combine(&self, cond: Fp4, inner: &MixState) -> Self {
+        MixState {
+            tot: self.tot + cond * self.mul * inner.tot,
+            mul: self.mul * inner.mul,
+        }
+    }
+}
+
+pub(crate) struct RiscVCircuit<'a> {
+    method_id: &'a MethodId,
+    pub(crate) po2: u32,
+    globals: [Fp; GLOBAL_SIZE],
+}
+
+impl<'a> RiscVCircuit<'a> {
+    pub(crate) fn new(method_id: &'a MethodId) -> Self {
+        RiscVCircuit {
+            method_id,
+            po2: 0,
+            globals: [Fp::default(); GLOBAL_SIZE],
+        }
+    }
+
+    /// Reads the output registers and the trace size.
+    pub(crate) fn execute(&mut self, iop: &mut ReadIop) -> Result<()> {
+        for (i, reg) in iop.read_u32s(OUTPUT_REGS)?.iter().enumerate() {
+            self.globals[2 * i] = Fp::new(reg & 0xffff);
+            self.globals[2 * i + 1] = Fp::new(reg >> 16);
+        }
+        self.po2 = iop.read_u32s(1)?[0];
+        Ok(())
+    }
+
+    pub(crate) fn accumulate(&mut self, iop: &mut ReadIop) {
+        for i in 0..ACCUM_MIX_GLOBAL_SIZE {
+            self.globals[ACCUM_MIX_GLOBAL_OFFSET + i] = iop.random_fp();
+        }
+    }
+
+    pub(crate) fn check_code(&self, root: &Digest) -> Result<()> {
+        let expected = self
+            .po2
+            .checked_sub(MIN_CYCLES_PO2)
+            .and_then(|which| self.method_id.0.get(which as usize));
+        match expected {
+            Some(expected) if expected == root => Ok(()),
+            _ => Err(Error::MethodIdMismatch),
+        }
+    }
+
+    pub(crate) fn compute_polynomial(&self, eval_u: &[Fp4], poly_mix: Fp4) -> Fp4 {
+        let mut fp4s = vec![Fp4::default(); step::NUM_STEP_FP4S];
+        let mut cons = vec![MixState::default(); step::NUM_STEP_CONS];
+        for op in step::STEPS {
+            match *op {
+                Op::Const(out, val) => fp4s[out as usize] = Fp4::from(Fp::new(val)),
+                Op::Get(out, id) => fp4s[out as usize] = eval_u[id as usize],
+                Op::GetGlobal(out, reg) => {
+                    fp4s[out as usize] = Fp4::from(self.globals[reg as usize])
+                }
+                Op::Begin(out) => cons[out as usize] = MixState::default(),
+                Op::AssertZero(out, prev, val) => {
+                    cons[out as usize] =
+                        cons[prev as usize].assert_zero(fp4s[val as usize], poly_mix)
+                }
+                Op::Combine(out, prev, cond, inner) => {
+                    cons[out as usize] =
+                        cons[prev as usize].combine(fp4s[cond as usize], &cons[inner as usize])
+                }
+                Op::Add(out, a, b) => fp4s[out as usize] = fp4s[a as usize] + fp4s[b as usize],
+                Op::Sub(out, a, b) => fp4s[out as usize] = fp4s[a as usize] - fp4s[b as usize],
+                Op::Mul(out, a, b) => fp4s[out as usize] = fp4s[a as usize] * fp4s[b as usize],
+            }
+        }
+        cons[step::RESULT].tot
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    InvalidMethodId,
+    InvalidProof,
+    JournalMismatch,
+    MethodIdMismatch,
+    SealTooShort,
+    TooManyCycles,
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str(match self {
+            Self::InvalidMethodId => "The method ID is not the expected length",
+            Self::InvalidProof => "The seal does not contain a valid proof",
+            Self::JournalMismatch => "The journal does not match the seal",
+            Self::MethodIdMismatch => "The seal was not produced by the expected method",
+            Self::SealTooShort => "The seal ended before the proof was complete",
+            Self::TooManyCycles => "The seal claims more cycles than the circuit supports",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
@@ -0,0 +1,231 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The base field, integers modulo `P = 15 * 2^27 + 1`, mirroring
+//! `risc0/zkp/core/fp.h`.
+
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::rng::ShaRng;
+
+/// An element of the base field, always stored in reduced form.
+///
+/// Unlike the C++ type this uses a direct rather than Montgomery
+/// representation; the values observed through [Fp::as_u32] are identical.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Fp(u32);
+
+impl Fp {
+    /// The modulus of the field.
+    pub const P: u32 = 15 * (1 << 27) + 1;
+
+    /// Constructs a field element, reducing `val` modulo `P`.
+    pub const fn new(val: u32) -> Self {
+        Fp(val % Self::P)
+    }
+
+    pub const fn as_u32(self) -> u32 {
+        self.0
+    }
+
+    /// Draws a uniformly random element, consuming the same words from the
+    /// RNG as the C++ prover.
+    pub(crate) fn random(rng: &mut ShaRng) -> Self {
+        let hi = rng.generate() as u64;
+        let lo = rng.generate() as u64;
+        let mut val = hi << 32 | lo;
+        // Rejection sampling: retry if we are in the final partial copy of P.
+        while val.checked_add(Self::P as u64).is_none() {
+            val = rng.generate() as u64;
+        }
+        Fp((val % Self::P as u64) as u32)
+    }
+
+    pub fn pow(self, mut n: usize) -> Self {
+        let mut x = self;
+        let mut tot = Fp(1);
+        while n != 0 {
+            if n % 2 == 1 {
+                tot *= x;
+            }
+            n /= 2;
+            x *= x;
+        }
+        tot
+    }
+
+    /// Computes the multiplicative inverse, where the inverse of zero is
+    /// zero.
+    pub fn inv(self) -> Self {
+        self.pow((Self::P - 2) as usize)
+    }
+}
+
+impl From<u32> for Fp {
+    fn from(val: u32) -> Self {
+        Fp::new(val)
+    }
+}
+
+impl Add for Fp {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        let r = self.0 + rhs.0;
+        Fp(if r >= Self::P { r - Self::P } else { r })
+    }
+}
+
+impl Sub for Fp {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        let r = self.0.wrapping_sub(rhs.0);
+        Fp(if r > Self::P {
+            r.wrapping_add(Self::P)
+        } else {
+            r
+        })
+    }
+}
+
+impl Mul for Fp {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Fp(((self.0 as u64 * rhs.0 as u64) % Self::P as u64) as u32)
+    }
+}
+
+impl Neg for Fp {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Fp(0) - self
+    }
+}
+
+impl AddAssign for Fp {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Fp {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Fp {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+/// The 'forward' roots of unity, `ROU_FWD[i]` has order `2^i`.
+pub(crate) const ROU_FWD: [Fp; 28] = [
+    Fp(1),
+    Fp(2013265920),
+    Fp(284861408),
+    Fp(1801542727),
+    Fp(567209306),
+    Fp(740045640),
+    Fp(918899846),
+    Fp(1881002012),
+    Fp(1453957774),
+    Fp(65325759),
+    Fp(1538055801),
+    Fp(515192888),
+    Fp(483885487),
+    Fp(157393079),
+    Fp(1695124103),
+    Fp(2005211659),
+    Fp(1540072241),
+    Fp(88064245),
+    Fp(1542985445),
+    Fp(1269900459),
+    Fp(1461624142),
+    Fp(825701067),
+    Fp(682402162),
+    Fp(1311873874),
+    Fp(1164520853),
+    Fp(352275361),
+    Fp(18769),
+    Fp(137),
+];
+
+/// The 'reverse' roots of unity, `ROU_REV[i]` is the inverse of
+/// `ROU_FWD[i]`.
+pub(crate) const ROU_REV: [Fp; 28] = [
+    Fp(1),
+    Fp(2013265920),
+    Fp(1728404513),
+    Fp(1592366214),
+    Fp(196396260),
+    Fp(1253260071),
+    Fp(72041623),
+    Fp(1091445674),
+    Fp(145223211),
+    Fp(1446820157),
+    Fp(1030796471),
+    Fp(2010749425),
+    Fp(1827366325),
+    Fp(1239938613),
+    Fp(246299276),
+    Fp(596347512),
+    Fp(1893145354),
+    Fp(246074437),
+    Fp(1525739923),
+    Fp(1194341128),
+    Fp(1463599021),
+    Fp(704606912),
+    Fp(95395244),
+    Fp(15672543),
+    Fp(647517488),
+    Fp(584175179),
+    Fp(137728885),
+    Fp(749463956),
+];
+
+#[cfg(test)]
+mod test {
+    use super::{Fp, ROU_FWD, ROU_REV};
+
+    #[test]
+    fn arithmetic() {
+        let a = Fp::new(Fp::P - 1);
+        assert_eq!(a + Fp::new(2), Fp::new(1));
+        assert_eq!(Fp::new(1) - Fp::new(2), a);
+        assert_eq!(-Fp::new(1), a);
+        assert_eq!(a * a, Fp::new(1));
+        assert_eq!(Fp::new(Fp::P + 5), Fp::new(5));
+    }
+
+    #[test]
+    fn inverse() {
+        for x in [1, 2, 11, 137, 0x1234567, Fp::P - 1] {
+            let x = Fp::new(x);
+            assert_eq!(x * x.inv(), Fp::new(1));
+        }
+        assert_eq!(Fp::new(0).inv(), Fp::new(0));
+    }
+
+    #[test]
+    fn roots_of_unity() {
+        for i in 0..ROU_FWD.len() {
+            assert_eq!(ROU_FWD[i] * ROU_REV[i], Fp::new(1));
+            assert_eq!(ROU_FWD[i].pow(1 << i), Fp::new(1));
+            if i > 0 {
+                assert_ne!(ROU_FWD[i].pow(1 << (i - 1)), Fp::new(1));
+            }
+        }
+    }
+}
@@ -0,0 +1,203 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The degree four extension field `F_p[X] / (X^4 - 11)`, mirroring
+//! `risc0/zkp/core/fp4.h`.
+
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::{fp::Fp, rng::ShaRng};
+
+const BETA: Fp = Fp::new(11);
+const NBETA: Fp = Fp::new(Fp::P - 11);
+
+/// An element of the extension field, `e[0] + e[1] X + e[2] X^2 + e[3] X^3`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Fp4(pub [Fp; 4]);
+
+impl Fp4 {
+    pub const fn new(a: Fp, b: Fp, c: Fp, d: Fp) -> Self {
+        Fp4([a, b, c, d])
+    }
+
+    pub const fn from_fp(x: Fp) -> Self {
+        Fp4([x, Fp::new(0), Fp::new(0), Fp::new(0)])
+    }
+
+    pub const fn one() -> Self {
+        Self::from_fp(Fp::new(1))
+    }
+
+    pub(crate) fn random(rng: &mut ShaRng) -> Self {
+        let a = Fp::random(rng);
+        let b = Fp::random(rng);
+        let c = Fp::random(rng);
+        let d = Fp::random(rng);
+        Fp4([a, b, c, d])
+    }
+
+    pub fn pow(self, mut n: usize) -> Self {
+        let mut x = self;
+        let mut tot = Self::one();
+        while n != 0 {
+            if n % 2 == 1 {
+                tot *= x;
+            }
+            n /= 2;
+            x *= x;
+        }
+        tot
+    }
+
+    /// Computes the multiplicative inverse, where the inverse of zero is
+    /// zero. See `inv` in fp4.h for the derivation.
+    pub fn inv(self) -> Self {
+        let a = &self.0;
+        let mut b0 = a[0] * a[0] + BETA * (a[1] * (a[3] + a[3]) - a[2] * a[2]);
+        let mut b2 = a[0] * (a[2] + a[2]) - a[1] * a[1] + BETA * (a[3] * a[3]);
+        let c = b0 * b0 + BETA * b2 * b2;
+        let ic = c.inv();
+        b0 *= ic;
+        b2 *= ic;
+        Fp4([
+            a[0] * b0 + BETA * a[2] * b2,
+            -a[1] * b0 + NBETA * a[3] * b2,
+            -a[0] * b2 + a[2] * b0,
+            a[1] * b2 - a[3] * b0,
+        ])
+    }
+}
+
+impl From<Fp> for Fp4 {
+    fn from(x: Fp) -> Self {
+        Fp4::from_fp(x)
+    }
+}
+
+impl Add for Fp4 {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        let mut out = self;
+        out += rhs;
+        out
+    }
+}
+
+impl Sub for Fp4 {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        let mut out = self;
+        out -= rhs;
+        out
+    }
+}
+
+impl Neg for Fp4 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Fp4::default() - self
+    }
+}
+
+impl Mul<Fp> for Fp4 {
+    type Output = Self;
+    fn mul(self, rhs: Fp) -> Self {
+        Fp4(self.0.map(|x| x * rhs))
+    }
+}
+
+impl Mul for Fp4 {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let a = &self.0;
+        let b = &rhs.0;
+        Fp4([
+            a[0] * b[0] + NBETA * (a[1] * b[3] + a[2] * b[2] + a[3] * b[1]),
+            a[0] * b[1] + a[1] * b[0] + NBETA * (a[2] * b[3] + a[3] * b[2]),
+            a[0] * b[2] + a[1] * b[1] + a[2] * b[0] + NBETA * (a[3] * b[3]),
+            a[0] * b[3] + a[1] * b[2] + a[2] * b[1] + a[3] * b[0],
+        ])
+    }
+}
+
+impl AddAssign for Fp4 {
+    fn add_assign(&mut self, rhs: Self) {
+        for i in 0..4 {
+            self.0[i] += rhs.0[i];
+        }
+    }
+}
+
+impl SubAssign for Fp4 {
+    fn sub_assign(&mut self, rhs: Self) {
+        for i in 0..4 {
+            self.0[i] -= rhs.0[i];
+        }
+    }
+}
+
+impl MulAssign for Fp4 {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+/// Evaluates the polynomial with the given coefficients at `x`.
+pub(crate) fn poly_eval(coeffs: &[Fp4], x: Fp4) -> Fp4 {
+    let mut tot = Fp4::default();
+    let mut mul = Fp4::one();
+    for coeff in coeffs {
+        tot += mul * *coeff;
+        mul *= x;
+    }
+    tot
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Fp, Fp4};
+
+    fn sample(seed: u32) -> Fp4 {
+        let f = |i: u32| {
+            Fp::new(
+                seed.wrapping_mul(0x9e3779b9)
+                    .wrapping_add(i.wrapping_mul(0x7f4a7c15)),
+            )
+        };
+        Fp4::new(f(0), f(1), f(2), f(3))
+    }
+
+    #[test]
+    fn field_laws() {
+        for seed in 1..20 {
+            let a = sample(seed);
+            let b = sample(seed + 100);
+            let c = sample(seed + 200);
+            assert_eq!(a * (b + c), a * b + a * c);
+            assert_eq!((a * b) * c, a * (b * c));
+            assert_eq!(a * b, b * a);
+            assert_eq!(a * a.inv(), Fp4::one());
+            assert_eq!(a.pow(5), a * a * a * a * a);
+        }
+        assert_eq!(Fp4::default().inv(), Fp4::default());
+    }
+
+    #[test]
+    fn reduction() {
+        // Multiplication reduces by X^4 + 11, exactly as `operator*` in fp4.h
+        // does, so X^4 == -11.
+        let x = Fp4::new(Fp::new(0), Fp::new(1), Fp::new(0), Fp::new(0));
+        assert_eq!(x.pow(4), -Fp4::from(Fp::new(11)));
+    }
+}
@@ -0,0 +1,185 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! FRI low-degree test, mirroring `risc0/zkp/verify/fri.cpp`.
+
+use alloc::vec::Vec;
+
+use crate::{
+    fp::{Fp, ROU_FWD, ROU_REV},
+    fp4::Fp4,
+    merkle::MerkleTreeVerifier,
+    read_iop::ReadIop,
+    sha::hash_words,
+    Error, Result, FRI_FOLD, FRI_MIN_DEGREE, INV_RATE, QUERIES,
+};
+
+/// In-place inverse NTT, leaving the coefficients in bit-reversed order.
+fn interpolate_ntt(io: &mut [Fp4]) {
+    fn butterfly(io: &mut [Fp4], n: usize) {
+        if n == 0 {
+            return;
+        }
+        let half = 1 << (n - 1);
+        let step = ROU_REV[n];
+        let mut cur = Fp::new(1);
+        for i in 0..half {
+            let a = io[i];
+            let b = io[i + half];
+            io[i] = a + b;
+            io[i + half] = (a - b) * cur;
+            cur *= step;
+        }
+        let (lo, hi) = io.split_at_mut(half);
+        butterfly(lo, n - 1);
+        butterfly(hi, n - 1);
+    }
+    let n = io.len().trailing_zeros() as usize;
+    butterfly(io, n);
+    let norm = Fp::new(io.len() as u32).inv();
+    for x in io.iter_mut() {
+        *x = *x * norm;
+    }
+}
+
+fn bit_reverse(io: &mut [Fp4]) {
+    let n = io.len().trailing_zeros();
+    for i in 0..io.len() {
+        let rev = (i as u32).reverse_bits() >> (32 - n);
+        if (i as u32) < rev {
+            io.swap(i, rev as usize);
+        }
+    }
+}
+
+fn fold_eval(values: &mut [Fp4], mix: Fp4, s: usize, j: usize) -> Fp4 {
+    interpolate_ntt(values);
+    bit_reverse(values);
+    let root_po2 = (FRI_FOLD * s).trailing_zeros() as usize;
+    let inv_wk = ROU_REV[root_po2].pow(j);
+    let mut mul = Fp::new(1);
+    let mut tot = Fp4::default();
+    let mut mix_pow = Fp4::one();
+    for value in values.iter() {
+        tot += *value * mul * mix_pow;
+        mul *= inv_wk;
+        mix_pow *= mix;
+    }
+    tot
+}
+
+struct VerifyRoundInfo {
+    domain: usize,
+    merkle: MerkleTreeVerifier,
+    mix: Fp4,
+}
+
+impl VerifyRoundInfo {
+    fn new(iop: &mut ReadIop, in_domain: usize) -> Result<Self> {
+        let domain = in_domain / FRI_FOLD;
+        let merkle = MerkleTreeVerifier::new(iop, domain, FRI_FOLD * 4, QUERIES)?;
+        let mix = iop.random_fp4();
+        Ok(VerifyRoundInfo {
+            domain,
+            merkle,
+            mix,
+        })
+    }
+
+    fn verify_query(&self, iop: &mut ReadIop, pos: &mut usize, goal: &mut Fp4) -> Result<()> {
+        let quot = *pos / self.domain;
+        let group = *pos % self.domain;
+        let data = self.merkle.verify(iop, group)?;
+        let mut data4: Vec<Fp4> = (0..FRI_FOLD)
+            .map(|i| {
+                Fp4::new(
+                    data[i],
+                    data[FRI_FOLD + i],
+                    data[2 * FRI_FOLD + i],
+                    data[3 * FRI_FOLD + i],
+                )
+            })
+            .collect();
+        if data4[quot] != *goal {
+            return Err(Error::InvalidProof);
+        }
+        *goal = fold_eval(&mut data4, self.mix, self.domain, group);
+        *pos = group;
+        Ok(())
+    }
+}
+
+/// Verifies that the committed polynomial has degree below `deg`. `inner`
+/// checks a query position against the other commitments and returns the
+/// expected evaluation there.
+pub(crate) fn fri_verify<F>(iop: &mut ReadIop, mut deg: usize, mut inner: F) -> Result<()>
+where
+    F: FnMut(&mut ReadIop, usize) -> Result<Fp4>,
+{
+    let mut domain = deg * INV_RATE;
+    let orig_domain = domain;
+    let mut rounds = Vec::new();
+    while deg > FRI_MIN_DEGREE {
+        rounds.push(VerifyRoundInfo::new(iop, domain)?);
+        domain /= FRI_FOLD;
+        deg /= FRI_FOLD;
+    }
+    let final_coeffs = iop.read_fps(deg * 4)?;
+    let digest = hash_words(final_coeffs.iter().map(|x| x.as_u32()), false);
+    iop.commit(&digest);
+    let gen = ROU_FWD[domain.trailing_zeros() as usize];
+    for _ in 0..QUERIES {
+        let rng = iop.generate();
+        let mut pos = rng as usize % orig_domain;
+        let mut goal = inner(iop, pos)?;
+        for round in rounds.iter() {
+            round.verify_query(iop, &mut pos, &mut goal)?;
+        }
+        let x = gen.pow(pos);
+        let mut fx = Fp4::default();
+        let mut cur = Fp::new(1);
+        for i in 0..deg {
+            for j in 0..4 {
+                fx.0[j] += cur * final_coeffs[j * deg + i];
+            }
+            cur *= x;
+        }
+        if fx != goal {
+            return Err(Error::InvalidProof);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bit_reverse, interpolate_ntt};
+    use crate::{
+        fp::{Fp, ROU_FWD},
+        fp4::{poly_eval, Fp4},
+    };
+
+    #[test]
+    fn interpolate() {
+        // Evaluate a known polynomial at the 16th roots of unity and recover
+        // its coefficients.
+        let coeffs: [Fp4; 16] = core::array::from_fn(|i| Fp4::from(Fp::new(3 * i as u32 + 1)));
+        let root = ROU_FWD[4];
+        let mut values: [Fp4; 16] =
+            core::array::from_fn(|i| poly_eval(&coeffs, Fp4::from(root.pow(i))));
+        interpolate_ntt(&mut values);
+        bit_reverse(&mut values);
+        assert_eq!(values, coeffs);
+    }
+}
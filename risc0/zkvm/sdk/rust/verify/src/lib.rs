@@ -0,0 +1,269 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A pure-Rust verifier for receipts produced by the RISC Zero zkVM.
+//!
+//! This is a port of `risc0/zkp/verify` and `risc0/zkvm/verify` that does not
+//! depend on the C++ prover, and so can run anywhere `alloc` is available.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod circuit;
+mod err;
+mod fp;
+mod fp4;
+mod fri;
+mod merkle;
+mod read_iop;
+mod rng;
+mod sha;
+mod step;
+mod taps;
+
+use alloc::{vec, vec::Vec};
+
+use zkvm_core::{Digest, DIGEST_WORDS};
+
+use crate::{
+    circuit::RiscVCircuit,
+    fp::{Fp, ROU_FWD, ROU_REV},
+    fp4::{poly_eval, Fp4},
+    fri::fri_verify,
+    merkle::MerkleTreeVerifier,
+    read_iop::ReadIop,
+    sha::hash_words,
+    taps::{RegisterGroup, TapSet, NUM_REGISTER_GROUPS},
+};
+pub use crate::{
+    err::{Error, Result},
+    sha::hash_bytes,
+};
+
+pub(crate) const QUERIES: usize = 50;
+pub(crate) const INV_RATE: usize = 4;
+pub(crate) const FRI_FOLD: usize = 16;
+pub(crate) const FRI_MIN_DEGREE: usize = 256;
+const EXT_SIZE: usize = 4;
+const CHECK_SIZE: usize = INV_RATE * EXT_SIZE;
+
+const MIN_CYCLES_PO2: u32 = 9;
+const MAX_CYCLES_PO2: u32 = 20;
+
+/// The number of code roots in a [MethodId], one for each supported trace
+/// size.
+pub const CODE_DIGEST_COUNT: usize = (MAX_CYCLES_PO2 - MIN_CYCLES_PO2 + 1) as usize;
+
+/// The expected code merkle roots of a guest method, as written to the `.id`
+/// file next to the method's ELF.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MethodId([Digest; CODE_DIGEST_COUNT]);
+
+impl MethodId {
+    /// The size in bytes of a serialized method ID.
+    pub const BYTES: usize = CODE_DIGEST_COUNT * DIGEST_WORDS * 4;
+
+    /// Parses the contents of a `.id` file.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != Self::BYTES {
+            return Err(Error::InvalidMethodId);
+        }
+        let words: Vec<u32> = bytes
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+        Ok(MethodId(core::array::from_fn(|i| {
+            Digest::new(
+                words[i * DIGEST_WORDS..][..DIGEST_WORDS]
+                    .try_into()
+                    .unwrap(),
+            )
+        })))
+    }
+}
+
+/// Verifies that `seal` is a valid proof of an execution of the method
+/// identified by `method_id`, and that `journal` is what it committed.
+pub fn verify(method_id: &MethodId, seal: &[u32], journal: &[u8]) -> Result<()> {
+    verify_seal(method_id, seal)?;
+    check_journal(seal, journal)
+}
+
+fn check_journal(seal: &[u32], journal: &[u8]) -> Result<()> {
+    let len = *seal.get(DIGEST_WORDS).ok_or(Error::SealTooShort)?;
+    if journal.len() != len as usize {
+        return Err(Error::JournalMismatch);
+    }
+    let matches = if journal.len() > DIGEST_WORDS * 4 {
+        hash_bytes(journal).as_slice() == &seal[..DIGEST_WORDS]
+    } else {
+        seal.iter()
+            .flat_map(|word| word.to_le_bytes())
+            .zip(journal)
+            .all(|(a, b)| a == *b)
+    };
+    match matches {
+        true => Ok(()),
+        false => Err(Error::JournalMismatch),
+    }
+}
+
+fn verify_seal(method_id: &MethodId, seal: &[u32]) -> Result<()> {
+    let taps = TapSet::new(step::TAPS);
+    let mut iop = ReadIop::new(seal);
+    let mut circuit = RiscVCircuit::new(method_id);
+
+    // Read any execution state
+    circuit.execute(&mut iop)?;
+
+    // Get the size
+    let po2 = circuit.po2;
+    if po2 > MAX_CYCLES_PO2 {
+        return Err(Error::TooManyCycles);
+    }
+    let size = 1 << po2;
+    let domain = size * INV_RATE;
+
+    // Read the code + data merkle roots
+    let code_merkle = MerkleTreeVerifier::new(
+        &mut iop,
+        domain,
+        taps.group_size(RegisterGroup::Code),
+        QUERIES,
+    )?;
+    let data_merkle = MerkleTreeVerifier::new(
+        &mut iop,
+        domain,
+        taps.group_size(RegisterGroup::Data),
+        QUERIES,
+    )?;
+
+    // Verify the code is what we expect
+    circuit.check_code(code_merkle.root())?;
+
+    // Prep accumulation
+    circuit.accumulate(&mut iop);
+    let accum_merkle = MerkleTreeVerifier::new(
+        &mut iop,
+        domain,
+        taps.group_size(RegisterGroup::Accum),
+        QUERIES,
+    )?;
+
+    // Set the poly mix value
+    let poly_mix = iop.random_fp4();
+    let check_merkle = MerkleTreeVerifier::new(&mut iop, domain, CHECK_SIZE, QUERIES)?;
+
+    let z = iop.random_fp4();
+    let back_one = ROU_REV[po2 as usize];
+
+    // Read the U coeffs, and define the tap variables
+    let num_taps = taps.taps_size();
+    let coeff_u = iop.read_fp4s(num_taps + CHECK_SIZE)?;
+    let hash_u = hash_words(coeff_u.iter().flat_map(|x| x.0).map(|x| x.as_u32()), false);
+    iop.commit(&hash_u);
+
+    // Now, convert to evaluated values
+    let mut cur_pos = 0;
+    let mut eval_u = Vec::with_capacity(num_taps);
+    for reg in &taps.regs {
+        let coeffs = &coeff_u[cur_pos..cur_pos + reg.backs.len()];
+        for &back in &reg.backs {
+            eval_u.push(poly_eval(coeffs, z * back_one.pow(back)));
+        }
+        cur_pos += reg.backs.len();
+    }
+
+    let result = circuit.compute_polynomial(&eval_u, poly_mix);
+
+    // Now generate the check polynomial
+    let mut check = Fp4::default();
+    let remap = [0, 2, 1, 3];
+    for (i, rmi) in remap.into_iter().enumerate() {
+        let zi = z.pow(i);
+        for j in 0..EXT_SIZE {
+            let mut basis = Fp4::default();
+            basis.0[j] = Fp::new(1);
+            check += coeff_u[num_taps + rmi + 4 * j] * zi * basis;
+        }
+    }
+    check *= (z * Fp::new(3)).pow(size) - Fp4::one();
+
+    // Make sure they match
+    if check != result {
+        return Err(Error::InvalidProof);
+    }
+
+    // Set the mix mix value
+    let mix = iop.random_fp4();
+
+    // Make the mixed U polynomials
+    let combo_count = taps.combos.len();
+    let mut combo_u: Vec<Vec<Fp4>> = taps
+        .combos
+        .iter()
+        .map(|combo| vec![Fp4::default(); combo.len()])
+        .collect();
+    let mut cur_mix = Fp4::one();
+    cur_pos = 0;
+    for reg in &taps.regs {
+        for i in 0..reg.backs.len() {
+            combo_u[reg.combo][i] += cur_mix * coeff_u[cur_pos + i];
+        }
+        cur_mix *= mix;
+        cur_pos += reg.backs.len();
+    }
+    // Handle check group
+    let mut check_u = Fp4::default();
+    for coeff in &coeff_u[cur_pos..cur_pos + CHECK_SIZE] {
+        check_u += cur_mix * *coeff;
+        cur_mix *= mix;
+    }
+    combo_u.push(vec![check_u]);
+
+    // Finally, do a FRI verification
+    let gen = ROU_FWD[domain.trailing_zeros() as usize];
+    fri_verify(&mut iop, size, |iop, idx| {
+        let x = Fp4::from(gen.pow(idx));
+        let mut rows: [Vec<Fp>; NUM_REGISTER_GROUPS] = Default::default();
+        rows[RegisterGroup::Accum as usize] = accum_merkle.verify(iop, idx)?;
+        rows[RegisterGroup::Code as usize] = code_merkle.verify(iop, idx)?;
+        rows[RegisterGroup::Data as usize] = data_merkle.verify(iop, idx)?;
+        let check_row = check_merkle.verify(iop, idx)?;
+        let mut cur = Fp4::one();
+        let mut tot = vec![Fp4::default(); combo_count + 1];
+        for reg in &taps.regs {
+            tot[reg.combo] += cur * rows[reg.group as usize][reg.offset];
+            cur *= mix;
+        }
+        for val in check_row {
+            tot[combo_count] += cur * val;
+            cur *= mix;
+        }
+        let mut ret = Fp4::default();
+        for (id, combo) in taps.combos.iter().enumerate() {
+            let num = tot[id] - poly_eval(&combo_u[id], x);
+            let mut divisor = Fp4::one();
+            for &back in combo {
+                divisor *= x - z * back_one.pow(back);
+            }
+            ret += num * divisor.inv();
+        }
+        let check_num = tot[combo_count] - combo_u[combo_count][0];
+        let check_divisor = x - z.pow(4);
+        ret += check_num * check_divisor.inv();
+        Ok(ret)
+    })
+}
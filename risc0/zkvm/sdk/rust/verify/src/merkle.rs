@@ -0,0 +1,101 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Merkle tree column proofs, mirroring `risc0/zkp/verify/merkle.h`. The top
+//! layers of each tree are sent once up front rather than in every proof.
+
+use alloc::{vec, vec::Vec};
+
+use zkvm_core::Digest;
+
+use crate::{
+    fp::Fp,
+    read_iop::ReadIop,
+    sha::{hash_pair, hash_words},
+    Error, Result,
+};
+
+pub(crate) struct MerkleTreeVerifier {
+    // The number of rows (leaves) of the tree.
+    row_size: usize,
+    // The number of field elements in each column.
+    col_size: usize,
+    // The size of the top layer sent in advance.
+    top_size: usize,
+    // The top of the tree as a heap, the root is at index 1.
+    top: Vec<Digest>,
+}
+
+impl MerkleTreeVerifier {
+    /// Reads the top layers from the IOP and commits to the root.
+    pub(crate) fn new(
+        iop: &mut ReadIop,
+        row_size: usize,
+        col_size: usize,
+        queries: usize,
+    ) -> Result<Self> {
+        if !row_size.is_power_of_two() {
+            return Err(Error::InvalidProof);
+        }
+        let layers = row_size.trailing_zeros() as usize;
+        let mut top_layer = 0;
+        for i in 1..layers {
+            if (1 << i) > queries {
+                break;
+            }
+            top_layer = i;
+        }
+        let top_size = 1 << top_layer;
+        let mut top = vec![Digest::default(); top_size * 2];
+        top[top_size..].copy_from_slice(&iop.read_digests(top_size)?);
+        for i in (1..top_size).rev() {
+            top[i] = hash_pair(&top[i * 2], &top[i * 2 + 1]);
+        }
+        iop.commit(&top[1]);
+        Ok(MerkleTreeVerifier {
+            row_size,
+            col_size,
+            top_size,
+            top,
+        })
+    }
+
+    pub(crate) fn root(&self) -> &Digest {
+        &self.top[1]
+    }
+
+    /// Verifies the proof for column `idx` and returns its values.
+    pub(crate) fn verify(&self, iop: &mut ReadIop, mut idx: usize) -> Result<Vec<Fp>> {
+        if idx >= self.row_size {
+            return Err(Error::InvalidProof);
+        }
+        let out = iop.read_fps(self.col_size)?;
+        let mut cur = hash_words(out.iter().map(|x| x.as_u32()), false);
+        idx += self.row_size;
+        while idx >= 2 * self.top_size {
+            let low_bit = idx % 2;
+            let other = iop.read_digests(1)?[0];
+            idx /= 2;
+            cur = if low_bit == 1 {
+                hash_pair(&other, &cur)
+            } else {
+                hash_pair(&cur, &other)
+            };
+        }
+        if self.top[idx] != cur {
+            return Err(Error::InvalidProof);
+        }
+        Ok(out)
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sequential access to a seal, mirroring `risc0/zkp/verify/read_iop.h`.
+
+use alloc::vec::Vec;
+
+use zkvm_core::{Digest, DIGEST_WORDS};
+
+use crate::{fp::Fp, fp4::Fp4, rng::ShaRng, Error, Result};
+
+pub(crate) struct ReadIop<'a> {
+    proof: &'a [u32],
+    rng: ShaRng,
+}
+
+impl<'a> ReadIop<'a> {
+    pub(crate) fn new(proof: &'a [u32]) -> Self {
+        ReadIop {
+            proof,
+            rng: ShaRng::new(),
+        }
+    }
+
+    /// Reads unverified words from the proof, to be checked later against a
+    /// commitment.
+    pub(crate) fn read_u32s(&mut self, count: usize) -> Result<&'a [u32]> {
+        if self.proof.len() < count {
+            return Err(Error::SealTooShort);
+        }
+        let (head, tail) = self.proof.split_at(count);
+        self.proof = tail;
+        Ok(head)
+    }
+
+    pub(crate) fn read_fps(&mut self, count: usize) -> Result<Vec<Fp>> {
+        Ok(self.read_u32s(count)?.iter().map(|x| Fp::new(*x)).collect())
+    }
+
+    pub(crate) fn read_fp4s(&mut self, count: usize) -> Result<Vec<Fp4>> {
+        let fps = self.read_fps(count * 4)?;
+        Ok(fps
+            .chunks_exact(4)
+            .map(|x| Fp4::new(x[0], x[1], x[2], x[3]))
+            .collect())
+    }
+
+    pub(crate) fn read_digests(&mut self, count: usize) -> Result<Vec<Digest>> {
+        let words = self.read_u32s(count * DIGEST_WORDS)?;
+        Ok(words
+            .chunks_exact(DIGEST_WORDS)
+            .map(|x| Digest::new(x.try_into().unwrap()))
+            .collect())
+    }
+
+    /// Applies a commitment to the transcript.
+    pub(crate) fn commit(&mut self, digest: &Digest) {
+        self.rng.mix(digest);
+    }
+
+    pub(crate) fn generate(&mut self) -> u32 {
+        self.rng.generate()
+    }
+
+    pub(crate) fn random_fp(&mut self) -> Fp {
+        Fp::random(&mut self.rng)
+    }
+
+    pub(crate) fn random_fp4(&mut self) -> Fp4 {
+        Fp4::random(&mut self.rng)
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The Fiat-Shamir transcript, mirroring `risc0/zkp/core/sha_rng.h`.
+
+use zkvm_core::{Digest, DIGEST_WORDS};
+
+use crate::sha::{hash_bytes, hash_pair};
+
+/// A sponge-like PRNG built on SHA-256, from which every verifier challenge
+/// is drawn.
+pub(crate) struct ShaRng {
+    pool0: Digest,
+    pool1: Digest,
+    pool_used: usize,
+}
+
+impl ShaRng {
+    pub(crate) fn new() -> Self {
+        ShaRng {
+            pool0: hash_bytes(b"Hello"),
+            pool1: hash_bytes(b"World"),
+            pool_used: 0,
+        }
+    }
+
+    /// Mixes a commitment into the entropy pool.
+    pub(crate) fn mix(&mut self, data: &Digest) {
+        let mut words = [0u32; DIGEST_WORDS];
+        for (i, word) in words.iter_mut().enumerate() {
+            *word = self.pool0.as_slice()[i] ^ data.as_slice()[i];
+        }
+        self.pool0 = Digest::new(words);
+        self.step();
+    }
+
+    pub(crate) fn generate(&mut self) -> u32 {
+        if self.pool_used == DIGEST_WORDS {
+            self.step();
+        }
+        let out = self.pool0.as_slice()[self.pool_used];
+        self.pool_used += 1;
+        out
+    }
+
+    fn step(&mut self) {
+        self.pool0 = hash_pair(&self.pool0, &self.pool1);
+        self.pool1 = hash_pair(&self.pool0, &self.pool1);
+        self.pool_used = 0;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ShaRng;
+    use crate::{fp::Fp, fp4::Fp4, sha::hash_bytes};
+
+    #[test]
+    fn field_draws() {
+        // Pinned to the same values as `ShaRng.FieldDraws` in
+        // risc0/zkp/core/test/sha_rng.cpp.
+        let mut rng = ShaRng::new();
+        rng.mix(&hash_bytes(b"abc"));
+        assert_eq!(Fp::random(&mut rng), Fp::new(963407152));
+        assert_eq!(Fp::random(&mut rng), Fp::new(35496));
+        assert_eq!(
+            Fp4::random(&mut rng),
+            Fp4::new(
+                Fp::new(244757508),
+                Fp::new(736377549),
+                Fp::new(1580838935),
+                Fp::new(1105023166)
+            )
+        );
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The SHA-256 variants used by the proof system, mirroring
+//! `risc0/zkp/core/sha256.h`. Word inputs are hashed as big-endian words
+//! without any byte swapping, and Merkle nodes are rolled up with a single
+//! unpadded compression.
+
+use zkvm_core::{
+    sha::{compress, INIT},
+    Digest,
+};
+
+/// Hashes a sequence of words. With `pad` unset, the final partial block is
+/// zero filled and no length is appended, matching `shaHash(..., false)`.
+pub(crate) fn hash_words<I: IntoIterator<Item = u32>>(words: I, pad: bool) -> Digest {
+    let mut state = INIT;
+    let mut block = [0u32; 16];
+    let mut cur = 0;
+    let mut size: u64 = 0;
+    for word in words {
+        block[cur] = word;
+        cur += 1;
+        size += 1;
+        if cur == 16 {
+            compress(&mut state, &block);
+            cur = 0;
+        }
+    }
+    if pad {
+        block[cur] = 0x80000000;
+        cur += 1;
+    }
+    block[cur..].fill(0);
+    if pad {
+        if cur > 14 {
+            compress(&mut state, &block);
+            block.fill(0);
+        }
+        let bits = size * 32;
+        block[14] = (bits >> 32) as u32;
+        block[15] = bits as u32;
+    }
+    if pad || cur != 0 {
+        compress(&mut state, &block);
+    }
+    Digest::new(state)
+}
+
+/// Combines two Merkle nodes.
+pub(crate) fn hash_pair(x: &Digest, y: &Digest) -> Digest {
+    let mut block = [0u32; 16];
+    block[..8].copy_from_slice(x.as_slice());
+    block[8..].copy_from_slice(y.as_slice());
+    let mut state = INIT;
+    compress(&mut state, &block);
+    Digest::new(state)
+}
+
+/// Standard SHA-256 of a byte string, with the digest words in big-endian
+/// order.
+pub fn hash_bytes(bytes: &[u8]) -> Digest {
+    let mut state = INIT;
+    let mut block = [0u32; 16];
+    let mut chunks = bytes.chunks_exact(64);
+    for chunk in &mut chunks {
+        for (word, bytes) in block.iter_mut().zip(chunk.chunks_exact(4)) {
+            *word = u32::from_be_bytes(bytes.try_into().unwrap());
+        }
+        compress(&mut state, &block);
+    }
+    let rest = chunks.remainder();
+    let mut last = [0u8; 64];
+    last[..rest.len()].copy_from_slice(rest);
+    last[rest.len()] = 0x80;
+    for (word, bytes) in block.iter_mut().zip(last.chunks_exact(4)) {
+        *word = u32::from_be_bytes(bytes.try_into().unwrap());
+    }
+    if rest.len() + 1 > 56 {
+        compress(&mut state, &block);
+        block.fill(0);
+    }
+    let bits = bytes.len() as u64 * 8;
+    block[14] = (bits >> 32) as u32;
+    block[15] = bits as u32;
+    compress(&mut state, &block);
+    Digest::new(state)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hash_bytes, hash_words};
+
+    #[test]
+    fn bytes() {
+        assert_eq!(
+            hash_bytes(b"").as_slice(),
+            &[
+                0xe3b0c442, 0x98fc1c14, 0x9afbf4c8, 0x996fb924, 0x27ae41e4, 0x649b934c, 0xa495991b,
+                0x7852b855
+            ]
+        );
+        assert_eq!(
+            hash_bytes(b"abc").as_slice(),
+            &[
+                0xba7816bf, 0x8f01cfea, 0x414140de, 0x5dae2223, 0xb00361a3, 0x96177a9c, 0xb410ff61,
+                0xf20015ad
+            ]
+        );
+        let long = [b'a'; 1000];
+        assert_eq!(
+            hash_bytes(&long).as_slice(),
+            &[
+                0x41edece4, 0x2d63e8d9, 0xbf515a9b, 0xa6932e1c, 0x20cbc9f5, 0xa5d13464, 0x5adb5db1,
+                0xb9737ea3
+            ]
+        );
+    }
+
+    #[test]
+    fn padded_words_match_bytes() {
+        let words = [0x61626364u32, 0x65666768, 0x696a6b6c];
+        assert_eq!(hash_words(words, true), hash_bytes(b"abcdefghijkl"));
+    }
+}
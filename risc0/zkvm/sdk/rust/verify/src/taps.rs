@@ -0,0 +1,135 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The set of registers and cycle offsets ('taps') read by the circuit
+//! constraints, mirroring `risc0/zkp/verify/taps.h`. Registers with the same
+//! pattern of taps share a 'combo', and are divided by the same value during
+//! verification.
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec,
+    vec::Vec,
+};
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) enum RegisterGroup {
+    Accum = 0,
+    Code = 1,
+    Data = 2,
+}
+
+pub(crate) const NUM_REGISTER_GROUPS: usize = 3;
+
+const GROUPS: [RegisterGroup; NUM_REGISTER_GROUPS] = [
+    RegisterGroup::Accum,
+    RegisterGroup::Code,
+    RegisterGroup::Data,
+];
+
+/// A register along with all of the cycles back at which it is tapped.
+pub(crate) struct Reg {
+    pub(crate) group: RegisterGroup,
+    pub(crate) offset: usize,
+    pub(crate) combo: usize,
+    pub(crate) backs: Vec<usize>,
+}
+
+pub(crate) struct TapSet {
+    // Registers ordered by group and then offset.
+    pub(crate) regs: Vec<Reg>,
+    // The distinct tap patterns, combo 0 is always just `{0}`.
+    pub(crate) combos: Vec<Vec<usize>>,
+    group_sizes: [usize; NUM_REGISTER_GROUPS],
+}
+
+impl TapSet {
+    /// Builds the tap set from a list of `(group, offset, back)` taps. Every
+    /// register of every group must be tapped at least once.
+    pub(crate) fn new(taps: &[(RegisterGroup, usize, usize)]) -> Self {
+        let mut all: BTreeMap<RegisterGroup, BTreeMap<usize, BTreeSet<usize>>> = BTreeMap::new();
+        for (group, offset, back) in taps {
+            all.entry(*group)
+                .or_default()
+                .entry(*offset)
+                .or_default()
+                .insert(*back);
+        }
+        let mut combos: Vec<Vec<usize>> = vec![vec![0]];
+        let mut regs = Vec::new();
+        let mut group_sizes = [0; NUM_REGISTER_GROUPS];
+        for group in GROUPS {
+            let group_regs = &all[&group];
+            let reg_count = group_regs.keys().next_back().unwrap() + 1;
+            assert_eq!(reg_count, group_regs.len(), "Every register must be tapped");
+            group_sizes[group as usize] = reg_count;
+            for (offset, backs) in group_regs {
+                let backs: Vec<usize> = backs.iter().copied().collect();
+                let combo = match combos.iter().position(|x| *x == backs) {
+                    Some(combo) => combo,
+                    None => {
+                        combos.push(backs.clone());
+                        combos.len() - 1
+                    }
+                };
+                regs.push(Reg {
+                    group,
+                    offset: *offset,
+                    combo,
+                    backs,
+                });
+            }
+        }
+        TapSet {
+            regs,
+            combos,
+            group_sizes,
+        }
+    }
+
+    /// The total number of taps over all registers.
+    pub(crate) fn taps_size(&self) -> usize {
+        self.regs.iter().map(|reg| reg.backs.len()).sum()
+    }
+
+    pub(crate) fn group_size(&self, group: RegisterGroup) -> usize {
+        self.group_sizes[group as usize]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{vec, vec::Vec};
+
+    use super::{RegisterGroup, TapSet};
+
+    #[test]
+    fn combos() {
+        let taps = [
+            (RegisterGroup::Accum, 0, 0),
+            (RegisterGroup::Accum, 0, 1),
+            (RegisterGroup::Code, 0, 0),
+            (RegisterGroup::Code, 1, 0),
+            (RegisterGroup::Data, 1, 1),
+            (RegisterGroup::Data, 1, 0),
+            (RegisterGroup::Data, 0, 2),
+        ];
+        let set = TapSet::new(&taps);
+        assert_eq!(set.combos, [vec![0], vec![0, 1], vec![2]]);
+        let combos: Vec<usize> = set.regs.iter().map(|reg| reg.combo).collect();
+        assert_eq!(combos, [1, 0, 0, 2, 1]);
+        assert_eq!(set.taps_size(), 7);
+        assert_eq!(set.group_size(RegisterGroup::Code), 2);
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+
+use zkvm_host::{Prover, Receipt};
+use zkvm_verify::{verify, Error, MethodId};
+
+const SHA_PATH: &str = "risc0/zkvm/sdk/rust/guest/test_sha";
+const MEM_PATH: &str = "risc0/zkvm/sdk/rust/guest/test_mem";
+
+fn load_method_id(path: &str) -> MethodId {
+    MethodId::from_bytes(&fs::read(format!("{}.id", path)).unwrap()).unwrap()
+}
+
+// Runs the SHA guest on `input`, laid out the way `env::read::<&[u8]>` expects.
+fn prove_sha(input: &[u8]) -> Receipt {
+    let mut words = vec![input.len() as u32];
+    words.extend(input.chunks(4).map(|chunk| {
+        let mut word = [0u8; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        u32::from_le_bytes(word)
+    }));
+    let mut prover = Prover::new(SHA_PATH).unwrap();
+    prover.add_input(&words).unwrap();
+    prover.run().unwrap()
+}
+
+#[test]
+fn agrees_with_prover() {
+    let method_id = load_method_id(SHA_PATH);
+    for input in ["", "abc", &"a".repeat(1000)] {
+        let receipt = prove_sha(input.as_bytes());
+        receipt.verify(SHA_PATH).unwrap();
+        let seal = receipt.get_seal().unwrap();
+        let journal = receipt.get_journal().unwrap();
+        assert_eq!(verify(&method_id, seal, journal), Ok(()));
+    }
+}
+
+#[test]
+fn rejects_tampering() {
+    let method_id = load_method_id(SHA_PATH);
+    let receipt = prove_sha(b"abc");
+    let seal = receipt.get_seal().unwrap();
+    let journal = receipt.get_journal().unwrap();
+
+    assert_eq!(
+        verify(&load_method_id(MEM_PATH), seal, journal),
+        Err(Error::MethodIdMismatch)
+    );
+
+    let mut bad_journal = journal.to_vec();
+    bad_journal[0] ^= 1;
+    assert_eq!(
+        verify(&method_id, seal, &bad_journal),
+        Err(Error::JournalMismatch)
+    );
+
+    let mut bad_seal = seal.to_vec();
+    let mid = bad_seal.len() / 2;
+    bad_seal[mid] ^= 1;
+    assert_eq!(
+        verify(&method_id, &bad_seal, journal),
+        Err(Error::InvalidProof)
+    );
+
+    assert_eq!(
+        verify(&method_id, &seal[..seal.len() - 1], journal),
+        Err(Error::SealTooShort)
+    );
+}
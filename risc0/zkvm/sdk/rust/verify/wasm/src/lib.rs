@@ -0,0 +1,65 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! WebAssembly bindings for receipt verification, so that a browser can check
+//! the receipts served by the battleship `web` example without trusting the
+//! server.
+
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+use zkvm_verify::MethodId;
+
+/// A receipt as encoded (with bincode) by the battleship `web` server.
+#[derive(Deserialize)]
+struct Receipt {
+    journal: Vec<u8>,
+    seal: Vec<u32>,
+}
+
+/// Verifies a bincode-encoded receipt against the contents of a method's
+/// `.id` file, returning the journal if the receipt is valid.
+#[wasm_bindgen]
+pub fn verify(receipt: &[u8], method_id: &[u8]) -> Result<Vec<u8>, JsValue> {
+    verify_receipt(receipt, method_id).map_err(|err| JsValue::from_str(&err))
+}
+
+fn verify_receipt(receipt: &[u8], method_id: &[u8]) -> Result<Vec<u8>, String> {
+    let receipt: Receipt = bincode::deserialize(receipt).map_err(|err| err.to_string())?;
+    let method_id = MethodId::from_bytes(method_id).map_err(|err| err.to_string())?;
+    zkvm_verify::verify(&method_id, &receipt.seal, &receipt.journal)
+        .map_err(|err| err.to_string())?;
+    Ok(receipt.journal)
+}
+
+#[cfg(test)]
+mod test {
+    use super::verify_receipt;
+
+    #[test]
+    fn malformed() {
+        let method_id = [0u8; zkvm_verify::MethodId::BYTES];
+        assert!(verify_receipt(&[1, 2, 3], &method_id).is_err());
+
+        // An empty journal followed by an empty seal.
+        let receipt = [0u8; 16];
+        assert_eq!(
+            verify_receipt(&receipt, &method_id[1..]),
+            Err(zkvm_verify::Error::InvalidMethodId.to_string())
+        );
+        assert_eq!(
+            verify_receipt(&receipt, &method_id),
+            Err(zkvm_verify::Error::SealTooShort.to_string())
+        );
+    }
+}
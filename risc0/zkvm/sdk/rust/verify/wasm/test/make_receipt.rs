@@ -0,0 +1,49 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Proves the SHA guest on a fixed input and writes the receipt the way the
+//! battleship `web` server does, along with the journal it expects back.
+
+use std::{env, fs};
+
+use serde::Serialize;
+use zkvm_host::Prover;
+
+#[derive(Serialize)]
+struct Receipt<'a> {
+    journal: &'a [u8],
+    seal: &'a [u32],
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let (method, out) = (&args[1], &args[2]);
+
+    // The input is a length-prefixed byte slice, as `env::read::<&[u8]>` expects.
+    let input = b"abc";
+    let mut words = vec![input.len() as u32];
+    words.push(u32::from_le_bytes([input[0], input[1], input[2], 0]));
+
+    let mut prover = Prover::new(method).unwrap();
+    prover.add_input(&words).unwrap();
+    let receipt = prover.run().unwrap();
+    let journal = receipt.get_journal().unwrap();
+    let encoded = bincode::serialize(&Receipt {
+        journal,
+        seal: receipt.get_seal().unwrap(),
+    })
+    .unwrap();
+    fs::write(format!("{}.b64", out), base64::encode(encoded)).unwrap();
+    fs::write(format!("{}.journal", out), journal).unwrap();
+}
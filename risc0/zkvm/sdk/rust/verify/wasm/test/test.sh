@@ -0,0 +1,19 @@
+#!/bin/bash
+# Usage: test.sh <make_receipt> <method> <verify.js> <wasm_bindgen outputs...>
+set -euo pipefail
+
+MAKE_RECEIPT=$1
+METHOD=$2
+RUNNER=$3
+shift 3
+
+BINDINGS=
+for file in "$@"; do
+  case "$file" in
+    *_bg.js | *.d.ts) ;;
+    *.js) BINDINGS=$file ;;
+  esac
+done
+
+"$MAKE_RECEIPT" "$METHOD" "$TEST_TMPDIR/receipt"
+node "$RUNNER" "$BINDINGS" "$TEST_TMPDIR/receipt.b64" "$METHOD.id" "$TEST_TMPDIR/receipt.journal"
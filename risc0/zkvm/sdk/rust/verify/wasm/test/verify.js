@@ -0,0 +1,38 @@
+// Copyright 2022 Risc0, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Usage: node verify.js <bindings.js> <receipt.b64> <method.id> [expected.journal]
+//
+// Verifies a base64 receipt, as served by the battleship web server, and prints
+// the journal in hex. If an expected journal is given, also checks that it
+// matches and that a tampered receipt is rejected.
+
+const assert = require('assert');
+const fs = require('fs');
+const path = require('path');
+
+const [bindings, receiptPath, idPath, journalPath] = process.argv.slice(2);
+const { verify } = require(path.resolve(bindings));
+
+const receipt = Buffer.from(fs.readFileSync(receiptPath, 'utf8'), 'base64');
+const methodId = fs.readFileSync(idPath);
+const journal = Buffer.from(verify(receipt, methodId));
+console.log(journal.toString('hex'));
+
+if (journalPath) {
+  assert.deepStrictEqual(journal, fs.readFileSync(journalPath));
+  const tampered = Buffer.from(receipt);
+  tampered[tampered.length - 1] ^= 1;
+  assert.throws(() => verify(tampered, methodId));
+}